@@ -0,0 +1,507 @@
+use super::*;
+use external::TraitImpl;
+use proc_macro2::Span;
+use sections::Section;
+use std::cell::RefCell;
+use syn::spanned::Spanned;
+
+/// The options set with `#[optional(...)]` (or `#[result_like(...)]`) on the enum itself
+pub(crate) struct Attributes {
+    /// The name of the attribute, `optional` or `result_like`
+    pub path: &'static str,
+    /// The sections that should be generated, in the order of `Section::ALL`
+    pub sections: Vec<Section>,
+    /// The trait implementations that should not be generated
+    pub no_impl: Vec<TraitImpl>,
+    /// Custom names for generated methods from `rename(method = "name")`
+    renames: Vec<(syn::Ident, String)>,
+    /// Prefix for all generated methods without a custom name
+    prefix: String,
+    /// Suffix for all generated methods without a custom name
+    suffix: String,
+    /// Visibility of all generated methods, if set with `vis = "..."`
+    vis: Option<syn::Visibility>,
+    /// Visibility of the methods of individual sections, if set with `vis(section = "...")`
+    section_vis: Vec<(Section, syn::Visibility)>,
+    /// Whether methods should be `const fn` where possible, set with `const`
+    pub const_fn: bool,
+    /// Custom panic message for `unwrap`, set with `unwrap_msg = "..."`
+    pub unwrap_msg: Option<String>,
+    /// Path to the `derive_optional` crate, set with `crate = "..."`
+    pub krate: syn::Path,
+    /// The `Some`-like variant of an enum with two unit variants, set with `some = Variant`
+    pub some: Option<syn::Ident>,
+    /// The methods that were generated so far. Used to detect unused renames
+    generated_methods: RefCell<Vec<String>>,
+}
+
+impl Attributes {
+    /// Parses the `#[<path>(...)]` attributes. Sections only exist for `#[optional(...)]`
+    pub fn parse(attrs: &[syn::Attribute], path: &'static str) -> Result<Self> {
+        let mut only = None;
+        let mut skip = None;
+        let mut no_impl = vec![];
+        let mut renames = vec![];
+        let mut rename_all = None;
+        let mut vis = None;
+        let mut section_vis = vec![];
+        let mut krate = None;
+        let mut const_fn = false;
+        let mut unwrap_msg = None;
+        let mut some = None;
+        // the spans of options that are checked once it is known whether `some` is set
+        let mut section_spans = vec![];
+        let mut unwrap_msg_span = None;
+
+        let mut error = Error::builder();
+        let has_sections = path == "optional";
+        let derive = match path {
+            "result_like" => "ResultLike",
+            "tri_state" => "TriState",
+            _ => "Optional",
+        };
+        for attr in attrs {
+            if !attr.path().is_ident(path) {
+                continue;
+            }
+            let res = attr.parse_nested_meta(|meta| {
+                let is_section_option = meta.path.is_ident("only")
+                    || meta.path.is_ident("skip")
+                    || (meta.path.is_ident("vis") && !meta.input.peek(syn::Token![=]));
+                if is_section_option && !has_sections {
+                    return Err(meta.error("sections are only available for `Optional`"));
+                }
+                // `TriState` has no `unwrap`
+                if meta.path.is_ident("unwrap_msg") && path == "tri_state" {
+                    let key = meta.path.to_token_stream();
+                    let msg = format!("`{}` is not available for `{}`", key, derive);
+                    return Err(meta.error(msg));
+                }
+                if meta.path.is_ident("only") {
+                    if only.is_some() {
+                        return Err(meta.error("duplicate `only` attribute"));
+                    }
+                    only = Some(parse_sections(&meta, &mut section_spans)?);
+                } else if meta.path.is_ident("skip") {
+                    if skip.is_some() {
+                        return Err(meta.error("duplicate `skip` attribute"));
+                    }
+                    skip = Some(parse_sections(&meta, &mut section_spans)?);
+                } else if meta.path.is_ident("no_impl") {
+                    parse_no_impl(&meta, &mut no_impl, path, derive)?;
+                } else if meta.path.is_ident("rename") {
+                    parse_renames(&meta, &mut renames)?;
+                } else if meta.path.is_ident("rename_all") {
+                    if rename_all.is_some() {
+                        return Err(meta.error("duplicate `rename_all` attribute"));
+                    }
+                    rename_all = Some(parse_rename_all(&meta)?);
+                } else if meta.path.is_ident("vis") {
+                    if meta.input.peek(syn::Token![=]) {
+                        if vis.is_some() {
+                            return Err(meta.error("duplicate `vis` attribute"));
+                        }
+                        vis = Some(parse_vis(&meta)?);
+                    } else {
+                        parse_section_vis(&meta, &mut section_vis, &mut section_spans)?;
+                    }
+                } else if meta.path.is_ident("const") {
+                    if const_fn {
+                        return Err(meta.error("duplicate `const` attribute"));
+                    }
+                    const_fn = true;
+                } else if meta.path.is_ident("unwrap_msg") {
+                    if unwrap_msg.is_some() {
+                        return Err(meta.error("duplicate `unwrap_msg` attribute"));
+                    }
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    unwrap_msg = Some(lit.value());
+                    unwrap_msg_span = Some(meta.path.span());
+                } else if meta.path.is_ident("crate") {
+                    if krate.is_some() {
+                        return Err(meta.error("duplicate `crate` attribute"));
+                    }
+                    let lit: syn::LitStr = meta.value()?.parse()?;
+                    krate = Some(lit.parse()?);
+                } else if meta.path.is_ident("some") && path == "optional" {
+                    if some.is_some() {
+                        return Err(meta.error("duplicate `some` attribute"));
+                    }
+                    some = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("unknown attribute"));
+                }
+                Ok(())
+            });
+            if let Err(e) = res {
+                error.push(e.into());
+            }
+        }
+        error.ok_or_build()?;
+
+        // `some = Variant` generates other methods and traits
+        let bool_like = some.is_some();
+        let mode = if bool_like { "with" } else { "without" };
+        let available = TraitImpl::available(path, bool_like);
+        for (t, span) in &no_impl {
+            if !available.contains(t) {
+                let msg = format!("`{}` is not implemented {} `some = ...`", t.name(), mode);
+                error.with(*span, msg);
+            }
+        }
+        if bool_like {
+            for (section, span) in &section_spans {
+                if !Section::BOOL_LIKE.contains(section) {
+                    let msg = format!(
+                        "the `{}` section is not generated with `some = ...`",
+                        section.name()
+                    );
+                    error.with(*span, msg);
+                }
+            }
+            if let Some(span) = unwrap_msg_span {
+                error.with(span, "`unwrap_msg` is not available with `some = ...`");
+            }
+        }
+        error.ok_or_build()?;
+        let no_impl = no_impl.into_iter().map(|(t, _)| t).collect();
+
+        let sections = match (only, skip) {
+            (Some(_), Some((span, _))) => {
+                let msg = "`only` and `skip` cannot be used together";
+                return Error::err(span, msg);
+            }
+            (Some((_, only)), None) => Section::ALL
+                .iter()
+                .copied()
+                .filter(|s| only.contains(s))
+                .collect(),
+            (None, Some((_, skip))) => Section::ALL
+                .iter()
+                .copied()
+                .filter(|s| !skip.contains(s))
+                .collect(),
+            (None, None) => Section::ALL.to_vec(),
+        };
+
+        let (prefix, suffix) = rename_all.unwrap_or_default();
+        let krate = krate.unwrap_or_else(|| syn::parse_quote! {::derive_optional});
+
+        Ok(Self {
+            path,
+            sections,
+            no_impl,
+            renames,
+            prefix,
+            suffix,
+            vis,
+            section_vis,
+            const_fn,
+            unwrap_msg,
+            krate,
+            some,
+            generated_methods: RefCell::new(vec![]),
+        })
+    }
+
+    pub fn implements(&self, trait_impl: TraitImpl) -> bool {
+        !self.no_impl.contains(&trait_impl)
+    }
+
+    /// The visibility set for all methods with `vis = "..."`, if any
+    pub fn vis(&self) -> Option<&syn::Visibility> {
+        self.vis.as_ref()
+    }
+
+    /// The visibility set for the methods in `section`, if any
+    pub fn visibility(&self, section: Section) -> Option<&syn::Visibility> {
+        self.section_vis
+            .iter()
+            .find(|(s, _)| *s == section)
+            .map(|(_, vis)| vis)
+            .or(self.vis.as_ref())
+    }
+
+    /// The name of the generated method `key`, after applying `rename` and `rename_all`.
+    ///
+    /// `default` is the name used without any renaming, which is only different from `key` for
+    /// methods that are named after the variants, like `is_some`.
+    pub fn method_name(&self, key: &str, default: &str) -> String {
+        self.generated_methods.borrow_mut().push(key.to_owned());
+        match self.renames.iter().find(|(k, _)| k == key) {
+            Some((_, name)) => name.clone(),
+            None => format!("{}{}{}", self.prefix, default, self.suffix),
+        }
+    }
+
+    /// Returns an error for every `rename` of a method that was not generated
+    pub fn check_unused_renames(&self) -> Result<()> {
+        let generated = self.generated_methods.borrow();
+        let mut error = Error::builder();
+        for (key, _) in &self.renames {
+            if !generated.iter().any(|m| key == m) {
+                let msg = format!("`{}` is not a method that is generated for this enum", key);
+                error.with_spanned(key, msg);
+            }
+        }
+        error.ok_or_build()
+    }
+}
+
+/// Parses the `#[<path>(...)]` attributes on a field of the `Some` variant and returns whether
+/// the field is marked with `skip`
+pub(crate) fn parse_field_skip(attrs: &[syn::Attribute], path: &str) -> syn::Result<bool> {
+    let mut skip = false;
+    for attr in attrs {
+        if !attr.path().is_ident(path) {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                if skip {
+                    return Err(meta.error("duplicate `skip` attribute"));
+                }
+                skip = true;
+                Ok(())
+            } else {
+                Err(meta.error("unknown attribute. Expected `skip`"))
+            }
+        })?;
+    }
+    Ok(skip)
+}
+
+/// Which variant an enum variant is marked as with `#[optional(some)]`, `#[optional(none)]` or
+/// `#[optional(default)]`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum VariantMarker {
+    Some,
+    None,
+    /// The `None`-like variant used for new `None`-like values
+    Default,
+}
+
+/// Parses the `#[optional(...)]` attributes on a variant and returns the variant it is marked as,
+/// if any
+pub(crate) fn parse_variant_marker(attrs: &[syn::Attribute]) -> syn::Result<Option<VariantMarker>> {
+    let mut marker = None;
+    for attr in attrs {
+        if !attr.path().is_ident("optional") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            let new = if meta.path.is_ident("some") {
+                VariantMarker::Some
+            } else if meta.path.is_ident("none") {
+                VariantMarker::None
+            } else if meta.path.is_ident("default") {
+                VariantMarker::Default
+            } else {
+                let msg = "unknown attribute. Expected `some`, `none` or `default`";
+                return Err(meta.error(msg));
+            };
+            marker = match (marker, new) {
+                (None, new) => Some(new),
+                // `default` already implies `none`
+                (Some(VariantMarker::None), VariantMarker::Default)
+                | (Some(VariantMarker::Default), VariantMarker::None) => {
+                    Some(VariantMarker::Default)
+                }
+                (Some(VariantMarker::Some), VariantMarker::Default)
+                | (Some(VariantMarker::Default), VariantMarker::Some) => {
+                    let msg = "`default` can only be used on a `None`-like variant";
+                    return Err(meta.error(msg));
+                }
+                _ => return Err(meta.error("a variant can only be marked once")),
+            };
+            Ok(())
+        })?;
+    }
+    Ok(marker)
+}
+
+/// Parses the list of traits in `no_impl(...)`
+fn parse_no_impl(
+    meta: &syn::meta::ParseNestedMeta,
+    no_impl: &mut Vec<(TraitImpl, Span)>,
+    path: &str,
+    derive: &str,
+) -> syn::Result<()> {
+    // traits that depend on `some = Variant` are checked after all options are parsed
+    let is_available = |t: &TraitImpl| {
+        TraitImpl::available(path, false).contains(t)
+            || TraitImpl::available(path, true).contains(t)
+    };
+    meta.parse_nested_meta(|meta| {
+        let name = meta.path.get_ident().map(|ident| ident.to_string());
+        match name.as_deref().and_then(TraitImpl::from_name) {
+            Some(t) if no_impl.iter().any(|(n, _)| *n == t) => Err(meta.error("duplicate trait")),
+            Some(t) if !is_available(&t) => {
+                let msg = format!("`{}` is not implemented by `{}`", t.name(), derive);
+                Err(meta.error(msg))
+            }
+            Some(t) => {
+                no_impl.push((t, meta.path.span()));
+                Ok(())
+            }
+            None => {
+                let names = TraitImpl::ALL
+                    .iter()
+                    .filter(|t| is_available(t))
+                    .map(|t| t.name())
+                    .collect::<Vec<_>>();
+                let msg = format!("unknown trait. Expected one of: {}", names.join(", "));
+                Err(meta.error(msg))
+            }
+        }
+    })
+}
+
+/// Parses the `method = "name"` pairs in `rename(...)`
+fn parse_renames(
+    meta: &syn::meta::ParseNestedMeta,
+    renames: &mut Vec<(syn::Ident, String)>,
+) -> syn::Result<()> {
+    meta.parse_nested_meta(|meta| {
+        let key = meta
+            .path
+            .get_ident()
+            .ok_or_else(|| meta.error("expected the name of a method"))?
+            .clone();
+        if renames.iter().any(|(k, _)| *k == key) {
+            return Err(meta.error("duplicate rename"));
+        }
+        let name = parse_ident_string(&meta)?;
+        renames.push((key, name));
+        Ok(())
+    })
+}
+
+/// Parses the `prefix = "..."` and `suffix = "..."` in `rename_all(...)`
+fn parse_rename_all(meta: &syn::meta::ParseNestedMeta) -> syn::Result<(String, String)> {
+    let (mut prefix, mut suffix) = (None, None);
+    meta.parse_nested_meta(|meta| {
+        let target = if meta.path.is_ident("prefix") {
+            &mut prefix
+        } else if meta.path.is_ident("suffix") {
+            &mut suffix
+        } else {
+            return Err(meta.error("unknown option. Expected `prefix` or `suffix`"));
+        };
+        if target.is_some() {
+            return Err(meta.error("duplicate option"));
+        }
+        let lit: syn::LitStr = meta.value()?.parse()?;
+        let value = lit.value();
+        if !value.chars().all(|c| c.is_alphanumeric() || c == '_') {
+            return Err(syn::Error::new(
+                lit.span(),
+                "expected a part of an identifier",
+            ));
+        }
+        *target = Some(value);
+        Ok(())
+    })?;
+    let prefix = prefix.unwrap_or_default();
+    if prefix.starts_with(|c: char| c.is_numeric()) {
+        return Err(meta.error("the prefix cannot start with a number"));
+    }
+    Ok((prefix, suffix.unwrap_or_default()))
+}
+
+/// Parses `= "name"` where `name` has to be a valid identifier
+fn parse_ident_string(meta: &syn::meta::ParseNestedMeta) -> syn::Result<String> {
+    let lit: syn::LitStr = meta.value()?.parse()?;
+    let ident: syn::Ident = lit.parse()?;
+    Ok(ident.to_string())
+}
+
+/// Parses `= "pub(...)"`, where an empty string means private
+fn parse_vis(meta: &syn::meta::ParseNestedMeta) -> syn::Result<syn::Visibility> {
+    let lit: syn::LitStr = meta.value()?.parse()?;
+    lit.parse()
+}
+
+/// Parses the `section = "pub(...)"` pairs in `vis(...)`
+fn parse_section_vis(
+    meta: &syn::meta::ParseNestedMeta,
+    section_vis: &mut Vec<(Section, syn::Visibility)>,
+    section_spans: &mut Vec<(Section, Span)>,
+) -> syn::Result<()> {
+    meta.parse_nested_meta(|meta| {
+        let section = parse_section(&meta)?;
+        if section_vis.iter().any(|(s, _)| *s == section) {
+            return Err(meta.error("duplicate section"));
+        }
+        section_spans.push((section, meta.path.span()));
+        section_vis.push((section, parse_vis(&meta)?));
+        Ok(())
+    })
+}
+
+/// Parses the name of a section
+fn parse_section(meta: &syn::meta::ParseNestedMeta) -> syn::Result<Section> {
+    let section = meta
+        .path
+        .get_ident()
+        .and_then(|ident| Section::from_name(&ident.to_string()));
+    section.ok_or_else(|| {
+        let names = Section::ALL.iter().map(|s| s.name()).collect::<Vec<_>>();
+        let msg = format!("unknown section. Expected one of: {}", names.join(", "));
+        meta.error(msg)
+    })
+}
+
+/// Parses the list of sections in `only(...)` or `skip(...)`
+fn parse_sections(
+    meta: &syn::meta::ParseNestedMeta,
+    section_spans: &mut Vec<(Section, Span)>,
+) -> syn::Result<(Span, Vec<Section>)> {
+    let mut sections = vec![];
+    meta.parse_nested_meta(|meta| {
+        let section = parse_section(&meta)?;
+        if sections.contains(&section) {
+            return Err(meta.error("duplicate section"));
+        }
+        section_spans.push((section, meta.path.span()));
+        sections.push(section);
+        Ok(())
+    })?;
+    Ok((meta.path.span(), sections))
+}
+
+/// Which variant without data of a `TriState` enum a variant is marked as with
+/// `#[tri_state(null)]` or `#[tri_state(undefined)]`
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TriStateMarker {
+    Null,
+    Undefined,
+}
+
+/// Parses the `#[tri_state(...)]` attributes on a variant and returns the variant it is marked
+/// as, if any
+pub(crate) fn parse_tri_state_marker(
+    attrs: &[syn::Attribute],
+) -> syn::Result<Option<TriStateMarker>> {
+    let mut marker = None;
+    for attr in attrs {
+        if !attr.path().is_ident("tri_state") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            let new = if meta.path.is_ident("null") {
+                TriStateMarker::Null
+            } else if meta.path.is_ident("undefined") {
+                TriStateMarker::Undefined
+            } else {
+                return Err(meta.error("unknown attribute. Expected `null` or `undefined`"));
+            };
+            if marker.is_some() {
+                return Err(meta.error("a variant can only be marked once"));
+            }
+            marker = Some(new);
+            Ok(())
+        })?;
+    }
+    Ok(marker)
+}
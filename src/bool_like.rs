@@ -0,0 +1,271 @@
+//! `#[optional(some = Variant)]` for enums with two unit variants, which are like `Option<()>` or
+//! `bool`. There is no contained value, so this doesn't use the `DataContainer` and sections.
+
+use super::*;
+use external::TraitImpl;
+use sections::Section;
+
+pub(crate) fn bool_like_internal(
+    name: syn::Ident,
+    vis: syn::Visibility,
+    generics: syn::Generics,
+    attrs: Attributes,
+    variants: syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    some_ident: syn::Ident,
+) -> Result<TokenStream> {
+    let mut error = Error::builder();
+    if variants.len() != 2 {
+        let msg = "`#[optional(some = ...)]` only works with exactly 2 enum variants";
+        error.with_spanned(&variants, msg);
+    }
+    for variant in variants.iter() {
+        if !is_unit_like(&variant.fields) {
+            let msg = "`#[optional(some = ...)]` only works with unit variants. Use \
+                       `#[optional(some)]` on the variant to mark a `Some`-like variant with data";
+            error.with_spanned(variant, msg);
+        }
+        match attributes::parse_variant_marker(&variant.attrs) {
+            Ok(None) => {}
+            Ok(Some(_)) => {
+                let msg = "variants can't be marked when using `#[optional(some = ...)]`";
+                error.with_spanned(&variant.ident, msg);
+            }
+            Err(e) => error.push(e.into()),
+        }
+    }
+    error.ok_or_build()?;
+
+    let (some_variant, none_variant) = match variants.iter().position(|v| v.ident == some_ident) {
+        Some(0) => (&variants[0], &variants[1]),
+        Some(_) => (&variants[1], &variants[0]),
+        None => {
+            let msg = format!("`{}` is not a variant of `{}`", some_ident, name);
+            return Error::err_spanned(some_ident, msg);
+        }
+    };
+    let some = none_with_defaults(&name, &some_variant.ident, &some_variant.fields);
+    let none = none_with_defaults(&name, &none_variant.ident, &none_variant.fields);
+    let some_pat = {
+        let ident = &some_variant.ident;
+        quote! { #name::#ident { .. } }
+    };
+    let some_name = some_variant.ident.to_string();
+    let none_name = none_variant.ident.to_string();
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let imp = quote! {impl #impl_generics};
+    let full_name = quote! {#name #ty_generics};
+
+    let func_for = |section: Section| {
+        let vis = attrs.visibility(section).unwrap_or(&vis);
+        quote! {#[inline] #vis fn}
+    };
+    let c_func_for = |section: Section| {
+        let vis = attrs.visibility(section).unwrap_or(&vis);
+        if attrs.const_fn {
+            quote! {#[inline] #vis const fn}
+        } else {
+            quote! {#[inline] #vis fn}
+        }
+    };
+    let method = |key: &str, default: &str| {
+        syn::Ident::new(
+            &attrs.method_name(key, default),
+            proc_macro2::Span::call_site(),
+        )
+    };
+    let (u, f) = (fresh_param(&generics, "U"), fresh_param(&generics, "F"));
+
+    let mut impl_block = TokenStream::new();
+
+    // Querying the contained values
+    if attrs.sections.contains(&Section::Querying) {
+        let c_func = c_func_for(Section::Querying);
+        let is_some = method("is_some", &format!("is_{}", some_name.to_case(Case::Snake)));
+        let is_none = method("is_none", &format!("is_{}", none_name.to_case(Case::Snake)));
+        let some_doc = format!("Returns `true` if the `{}` is `{}`.", name, some_name);
+        let none_doc = format!("Returns `true` if the `{}` is `{}`.", name, none_name);
+        impl_block.extend(quote! {
+            #[doc = #some_doc]
+            #c_func #is_some(&self) -> bool {
+                ::core::matches!(*self, #some_pat)
+            }
+
+            #[doc = #none_doc]
+            #c_func #is_none(&self) -> bool {
+                !::core::matches!(*self, #some_pat)
+            }
+        });
+    }
+
+    // Transforming contained values
+    if attrs.sections.contains(&Section::Transformers) {
+        let func = func_for(Section::Transformers);
+        let then = method("then", "then");
+        let then_some = method("then_some", "then_some");
+        let then_doc = format!(
+            "Returns `Some(f())` if the `{}` is `{}`, or `None` otherwise. Equivalent to `bool::then`.",
+            name, some_name,
+        );
+        let then_some_doc = format!(
+            "Returns `Some(t)` if the `{}` is `{}`, or `None` otherwise. Equivalent to `bool::then_some`.",
+            name, some_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #then_doc]
+            #func #then<#u, #f>(self, f: #f) -> ::core::option::Option<#u>
+            where
+                #f: FnOnce() -> #u,
+            {
+                if ::core::matches!(self, #some_pat) {
+                    ::core::option::Option::Some(f())
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+
+            #[doc = #then_some_doc]
+            #func #then_some<#u>(self, t: #u) -> ::core::option::Option<#u> {
+                if ::core::matches!(self, #some_pat) {
+                    ::core::option::Option::Some(t)
+                } else {
+                    ::core::option::Option::None
+                }
+            }
+        });
+    }
+
+    // Boolean operations on the values
+    if attrs.sections.contains(&Section::BoolOps) {
+        let c_func = c_func_for(Section::BoolOps);
+        let and = method("and", "and");
+        let or = method("or", "or");
+        let xor = method("xor", "xor");
+        let and_doc = format!(
+            "Returns `{some}` if both values are `{some}`, otherwise `{none}`. Like `&&` for `bool`.",
+            some = some_name, none = none_name,
+        );
+        let or_doc = format!(
+            "Returns `{some}` if either value is `{some}`, otherwise `{none}`. Like `||` for `bool`.",
+            some = some_name, none = none_name,
+        );
+        let xor_doc = format!(
+            "Returns `{some}` if exactly one of the values is `{some}`, otherwise `{none}`. Like `^` for `bool`.",
+            some = some_name, none = none_name,
+        );
+        impl_block.extend(quote! {
+            #[doc = #and_doc]
+            #c_func #and(self, other: Self) -> Self {
+                if ::core::matches!(self, #some_pat) && ::core::matches!(other, #some_pat) {
+                    #some
+                } else {
+                    #none
+                }
+            }
+
+            #[doc = #or_doc]
+            #c_func #or(self, other: Self) -> Self {
+                if ::core::matches!(self, #some_pat) || ::core::matches!(other, #some_pat) {
+                    #some
+                } else {
+                    #none
+                }
+            }
+
+            #[doc = #xor_doc]
+            #c_func #xor(self, other: Self) -> Self {
+                if ::core::matches!(self, #some_pat) != ::core::matches!(other, #some_pat) {
+                    #some
+                } else {
+                    #none
+                }
+            }
+        });
+    }
+
+    let mut additional_impls = TokenStream::new();
+
+    // Self: From<bool>
+    if attrs.implements(TraitImpl::From) {
+        let doc = format!(
+            "Converts `true` to `{}` and `false` to `{}`.",
+            some_name, none_name
+        );
+        additional_impls.extend(quote! {
+            #[automatically_derived]
+            #imp ::core::convert::From<bool> for #full_name #where_clause {
+                #[doc = #doc]
+                fn from(b: bool) -> Self {
+                    if b {
+                        #some
+                    } else {
+                        #none
+                    }
+                }
+            }
+        });
+    }
+
+    // bool: From<Self>
+    if attrs.implements(TraitImpl::From) {
+        additional_impls.extend(quote! {
+            #[automatically_derived]
+            #imp ::core::convert::From<#full_name> for bool #where_clause {
+                fn from(src: #full_name) -> Self {
+                    ::core::matches!(src, #some_pat)
+                }
+            }
+        });
+    }
+
+    // Self: Not
+    if attrs.implements(TraitImpl::Not) {
+        additional_impls.extend(quote! {
+            #[automatically_derived]
+            #imp ::core::ops::Not for #full_name #where_clause {
+                type Output = Self;
+
+                fn not(self) -> Self {
+                    if ::core::matches!(self, #some_pat) {
+                        #none
+                    } else {
+                        #some
+                    }
+                }
+            }
+        });
+    }
+
+    // Self: Default
+    if attrs.implements(TraitImpl::Default) {
+        let doc = format!("Returns `{}`. Equivalent to `Option::default`.", none_name);
+        additional_impls.extend(quote! {
+            #[automatically_derived]
+            #imp ::core::default::Default for #full_name #where_clause {
+                #[doc = #doc]
+                fn default() -> Self {
+                    #none
+                }
+            }
+        });
+    }
+
+    attrs.check_unused_renames()?;
+
+    let krate = &attrs.krate;
+    Ok(quote! {
+        #[allow(dead_code)]
+        #imp #full_name #where_clause {
+            #impl_block
+        }
+
+        #additional_impls
+
+        // fails to compile if the path set with `crate = "..."` does not resolve
+        const _: () = {
+            #[allow(unused_imports)]
+            use #krate as _;
+        };
+    })
+}
@@ -1,14 +1,283 @@
 use super::*;
+use crate::generics::remove_bounds_of;
+use crate::sections::Section;
+
+pub(crate) fn add_external(container: &DataContainer, additional_impls: &mut TokenStream) {
+    #[allow(unused_variables)]
+    #[rustfmt::skip]
+    let DataContainer {
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
+    } = *container;
+
+    // these methods belong to the transformers section, which decides whether they are added and
+    // their visibility
+    let transformers = attrs.sections.contains(&Section::Transformers);
+    let func = {
+        let vis = attrs.visibility(Section::Transformers).unwrap_or(vis);
+        quote! {#[inline] #vis fn}
+    };
+
+    // copied and cloned on #name<&#some_ty> and #name<&mut #some_ty>
+    if let (true, Some(generic)) = (is_generic, generic) {
+        let none_arms = container.none_arms();
+        let u = container.param("U");
+        let a = container.lifetime("a");
+        let name_u = container.name_with(&u);
+        let u_bounds = container.bounds_for(&u);
+
+        // the generic parameter is replaced by `U`, and its bounds move to the where clause, since
+        // they now have to hold for both `&'a U` and `U` (or `Result<U, E>` and `U` below)
+        let mut u_generics = generics.clone();
+        remove_bounds_of(&mut u_generics, generic);
+        for param in u_generics.type_params_mut() {
+            if param.ident == *generic {
+                param.ident = u.clone();
+            }
+        }
+        let mut ref_generics = u_generics.clone();
+        ref_generics.params.insert(0, syn::parse_quote! { #a });
+        let (ref_imp, _, ref_where) = ref_generics.split_for_impl();
+        let ref_predicates = ref_where.map(|w| &w.predicates);
+
+        if transformers {
+            for reference in [quote! { &#a }, quote! { &#a mut }] {
+                let ref_u = quote! { #reference #u };
+                let name_ref_u = container.name_with(&ref_u);
+                let ref_bounds = container.bounds_for(&ref_u);
+
+                let copied = container.method("copied");
+                let copied_arms = container
+                    .some_arms(quote! {x}, |v| container.some_variant_with(v, quote! {*x}));
+                let copied_doc = format!(
+                    "Maps a `{name}<{ref_u}>` to a `{name}<{u}>` by copying the contents. Equivalent to `Option::copied`.",
+                    name = name, ref_u = ref_u.to_string().replace(' ', ""), u = u,
+                );
+                let cloned = container.method("cloned");
+                let cloned_arms = container.some_arms(quote! {x}, |v| {
+                    container.some_variant_with(v, quote! {::core::clone::Clone::clone(x)})
+                });
+                let cloned_doc = format!(
+                    "Maps a `{name}<{ref_u}>` to a `{name}<{u}>` by cloning the contents. Equivalent to `Option::cloned`.",
+                    name = name, ref_u = ref_u.to_string().replace(' ', ""), u = u,
+                );
+                additional_impls.extend(quote! {
+                    #[allow(dead_code)]
+                    impl #ref_imp #name_ref_u
+                    where
+                        #ref_bounds
+                        #u_bounds
+                        #ref_predicates
+                    {
+                        #[doc = #copied_doc]
+                        #func #copied(self) -> #name_u
+                        where
+                            #u: ::core::marker::Copy,
+                        {
+                            match self {
+                                #copied_arms
+                                #none_arms
+                            }
+                        }
+
+                        #[doc = #cloned_doc]
+                        #func #cloned(self) -> #name_u
+                        where
+                            #u: ::core::clone::Clone,
+                        {
+                            match self {
+                                #cloned_arms
+                                #none_arms
+                            }
+                        }
+                    }
+                });
+            }
+        }
+
+        // transpose on #name<Result<#some_ty, E>> and #name<Option<#some_ty>>. no_impl(Transpose)
+        // disables it along with the extension trait below
+        if transformers && attrs.implements(TraitImpl::Transpose) {
+            let e = container.param("E");
+            let res = quote! {::core::result::Result};
+            let transpose = container.method("transpose");
+
+            let (opt_imp, _, opt_where) = u_generics.split_for_impl();
+            let opt_predicates = opt_where.map(|w| &w.predicates);
+            let mut res_generics = u_generics.clone();
+            res_generics.params.push(syn::parse_quote! { #e });
+            let (res_imp, _, res_where) = res_generics.split_for_impl();
+            let res_predicates = res_where.map(|w| &w.predicates);
+
+            let res_u = quote! { #res<#u, #e> };
+            let name_res_u = container.name_with(&res_u);
+            let res_bounds = container.bounds_for(&res_u);
+            let res_arms = container.some_arms(quote! {x}, |v| {
+                let some = container.some_variant_with(v, quote! {x});
+                quote! {
+                    match x {
+                        #res::Ok(x) => #res::Ok(#some),
+                        #res::Err(e) => #res::Err(e),
+                    }
+                }
+            });
+            let res_none_arms = container.none_arms_wrapped(|none| quote! { #res::Ok(#none) });
+            let res_doc = format!(
+                "Transposes a `{name}` of a `Result` into a `Result` of a `{name}`. Equivalent to `Option::transpose`.",
+                name = name,
+            );
+            additional_impls.extend(quote! {
+                #[allow(dead_code)]
+                impl #res_imp #name_res_u
+                where
+                    #res_bounds
+                    #u_bounds
+                    #res_predicates
+                {
+                    #[doc = #res_doc]
+                    #func #transpose(self) -> #res<#name_u, #e> {
+                        match self {
+                            #res_arms
+                            #res_none_arms
+                        }
+                    }
+                }
+            });
+
+            let opt_u = quote! { #opt<#u> };
+            let name_opt_u = container.name_with(&opt_u);
+            let opt_bounds = container.bounds_for(&opt_u);
+            let opt_arms = container.some_arms(quote! {x}, |v| {
+                let some = container.some_variant_with(v, quote! {x});
+                quote! {
+                    match x {
+                        #opt::Some(x) => #opt::Some(#some),
+                        #opt::None => #opt::None,
+                    }
+                }
+            });
+            let opt_none_arms = container.none_arms_wrapped(|none| quote! { #opt::Some(#none) });
+            let opt_doc = format!(
+                "Transposes a `{name}` of an `Option` into an `Option` of a `{name}`. Equivalent to `Option::transpose`, with the `Option` inside.",
+                name = name,
+            );
+            additional_impls.extend(quote! {
+                #[allow(dead_code)]
+                impl #opt_imp #name_opt_u
+                where
+                    #opt_bounds
+                    #u_bounds
+                    #opt_predicates
+                {
+                    #[doc = #opt_doc]
+                    #func #transpose(self) -> #opt<#name_u> {
+                        match self {
+                            #opt_arms
+                            #opt_none_arms
+                        }
+                    }
+                }
+            });
+        }
+    }
+
+    // transpose on Result<#name<#some_ty>, E> and Option<#name<#some_ty>>, with an extension trait
+    if let (true, Some(generic), true) =
+        (is_generic, generic, attrs.implements(TraitImpl::Transpose))
+    {
+        let e = container.param("E");
+        let res = quote! {::core::result::Result};
+        let transpose = container.method("transpose");
+        let trait_name = quote::format_ident!("{}Transpose", name);
+        let none_arms = container.none_arms();
+
+        let mut res_generics = generics.clone();
+        res_generics.params.push(syn::parse_quote! { #e });
+        let (res_imp, _, res_where) = res_generics.split_for_impl();
+        let res_predicates = res_where.map(|w| &w.predicates);
+        let predicates = generics.where_clause.as_ref().map(|w| &w.predicates);
+
+        let res_t = quote! { #res<#generic, #e> };
+        let res_bounds = container.bounds_for(&res_t);
+        let opt_t = quote! { #opt<#generic> };
+        let opt_bounds = container.bounds_for(&opt_t);
+        let name_res_t = container.name_with(&res_t);
+        let name_opt_t = container.name_with(&opt_t);
+
+        let inner_arms = |wrap: TokenStream| {
+            let some_arms = container.some_arms(quote! {x}, |v| {
+                container.some_variant_with(v, quote! {#wrap(x)})
+            });
+            quote! { #some_arms #none_arms }
+        };
+        let res_arms = inner_arms(quote! {#res::Ok});
+        let opt_arms = inner_arms(quote! {#opt::Some});
+        let some_from_err = container.some_with(quote! {#res::Err(e)});
+        let some_from_none = container.some_with(quote! {#opt::None});
+
+        let trait_doc = format!(
+            "Adds `{transpose}` to `Result<{name}<..>, E>` and `Option<{name}<..>>`, which turns them into a `{name}` of a `Result` or an `Option`.",
+            transpose = transpose, name = name,
+        );
+        let method_doc = format!(
+            "Transposes a `Result` or an `Option` of a `{name}` into a `{name}` of a `Result` or an `Option`.",
+            name = name,
+        );
+        additional_impls.extend(quote! {
+            #[doc = #trait_doc]
+            #[allow(dead_code)]
+            #vis trait #trait_name {
+                /// The transposed type
+                type Output;
+
+                #[doc = #method_doc]
+                fn #transpose(self) -> Self::Output;
+            }
+
+            #[automatically_derived]
+            impl #res_imp #trait_name for #res<#full_name, #e>
+            where
+                #res_bounds
+                #res_predicates
+            {
+                type Output = #name_res_t;
+
+                #[inline]
+                fn #transpose(self) -> Self::Output {
+                    match self {
+                        #res::Ok(x) => match x {
+                            #res_arms
+                        },
+                        #res::Err(e) => #some_from_err,
+                    }
+                }
+            }
+
+            #[automatically_derived]
+            #imp #trait_name for #opt<#full_name>
+            where
+                #opt_bounds
+                #predicates
+            {
+                type Output = #name_opt_t;
+
+                #[inline]
+                fn #transpose(self) -> Self::Output {
+                    match self {
+                        #opt::Some(x) => match x {
+                            #opt_arms
+                        },
+                        #opt::None => #some_from_none,
+                    }
+                }
+            }
+        });
+    }
 
-pub(crate) fn add_external(_container: &DataContainer, _additional_impls: &mut TokenStream) {
     // TODO: unzip on #name<(#some_ty, U)>
-    // TODO: copied on #name<&#some_ty> where #some_ty: Copy
-    // TODO: cloned on #name<&#some_ty> where #some_ty: Clone
-    // TODO: copied on #name<&mut #some_ty> where #some_ty: Copy
-    // TODO: cloned on #name<&mut #some_ty> where #some_ty: Clone
-    // TODO: transpose on #name<Result<#some_ty, E>>
-    // TODO: transpose on #name<Option<#some_ty>>
-    // TODO: transpose on Option<#name<#some_ty>>
     // TODO: flatten on #name<#name<#some_ty>>
     // TODO: flatten on #name<Option<#some_ty>> -> #name<#some_ty>
     // TODO: flatten on Option<#name<#some_ty>> -> #name<#some_ty>
@@ -4,33 +4,40 @@ pub(crate) fn add_external(container: &DataContainer, additional_impls: &mut Tok
     #[allow(unused_variables)]
     #[rustfmt::skip]
     let DataContainer {
-        ref name, ref full_name, ref full_name_string, ref some, ref none, ref some_name, ref none_name,
-        ref some_name_snake, ref none_name_snake, ref some_ty, ref some_ty_name, is_generic, ref bounds, ref imp,
-        ref func, ref c_func, ref opt
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
     } = *container;
 
+    let x = container.some_value("x");
+    let some_x = container.some_pat(quote! {x});
+    let some_from_x = container.some_with(quote! {x});
+    let none_where = container.imp_where_with(container.none_default_bounds());
+
     // Self: From<#some_ty>
-    {
+    if attrs.implements(TraitImpl::From) {
         let doc = format!("Moves the value into a `{}`.", some_name);
         additional_impls.extend(quote! {
             #[automatically_derived]
-            #imp ::std::convert::From<#some_ty> for #full_name {
+            #imp ::core::convert::From<#some_ty> for #full_name #imp_where {
                 #[doc = #doc]
                 fn from(x: #some_ty) -> Self {
-                    #some(x)
+                    #some_from_x
                 }
             }
         });
     }
 
     // Self: From<Option>
-    {
+    if attrs.implements(TraitImpl::From) {
         additional_impls.extend(quote! {
             #[automatically_derived]
-            #imp ::std::convert::From<#opt<#some_ty>> for #full_name {
+            #imp ::core::convert::From<#opt<#some_ty>> for #full_name #none_where {
                 fn from(src: #opt<#some_ty>) -> Self {
                     match src {
-                        #opt::Some(x) => #some(x),
+                        #opt::Some(x) => #some_from_x,
                         _ => #none,
                     }
                 }
@@ -39,13 +46,13 @@ pub(crate) fn add_external(container: &DataContainer, additional_impls: &mut Tok
     }
 
     // Option: From<Self>
-    {
+    if attrs.implements(TraitImpl::From) {
         additional_impls.extend(quote! {
             #[automatically_derived]
-            #imp ::std::convert::From<#full_name> for #opt<#some_ty> {
+            #imp ::core::convert::From<#full_name> for #opt<#some_ty> #imp_where {
                 fn from(src: #full_name) -> Self {
                     match src {
-                        #some(x) => #opt::Some(x),
+                        #some_x => #opt::Some(#x),
                         _ => #opt::None,
                     }
                 }
@@ -54,14 +61,14 @@ pub(crate) fn add_external(container: &DataContainer, additional_impls: &mut Tok
     }
 
     // Self: Default
-    {
+    if attrs.implements(TraitImpl::Default) {
         let doc = format!(
             "Returns a `{}` value. Equivalent to `Option::default`.",
             none_name,
         );
         additional_impls.extend(quote! {
             #[automatically_derived]
-            #imp ::std::default::Default for #full_name {
+            #imp ::core::default::Default for #full_name #none_where {
                 #[doc = #doc]
                 fn default() -> Self {
                     #none
@@ -71,19 +78,23 @@ pub(crate) fn add_external(container: &DataContainer, additional_impls: &mut Tok
     }
 
     // Self: IntoIterator
-    {
+    if attrs.implements(TraitImpl::IntoIterator) {
         let doc = format!(
             "Returns an iterator over the possibly contained value. Equivalent to `Option::into_iter`.",
         );
         additional_impls.extend(quote! {
             #[automatically_derived]
-            #imp ::std::iter::IntoIterator for #full_name {
+            #imp ::core::iter::IntoIterator for #full_name #imp_where {
                 type Item = #some_ty;
-                type IntoIter = ::std::option::IntoIter<#some_ty>;
+                type IntoIter = ::core::option::IntoIter<#some_ty>;
 
                 #[doc = #doc]
                 fn into_iter(self) -> Self::IntoIter {
-                    #opt::from(self).into_iter()
+                    match self {
+                        #some_x => #opt::Some(#x),
+                        _ => #opt::None,
+                    }
+                    .into_iter()
                 }
             }
         });
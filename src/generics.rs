@@ -0,0 +1,170 @@
+use super::*;
+use syn::visit::Visit;
+use syn::visit_mut::VisitMut;
+
+/// Returns the generic parameter that is changed by methods like `map`, if any. All other generic
+/// parameters are passed through unchanged.
+///
+/// This is the generic type that occurs in `some_ty`, which can be nested arbitrarily deep, like
+/// the `T` in `Vec<Box<T>>`. If more than one generic type occurs in `some_ty`, it is unclear which
+/// one should be changed, so `None` is returned.
+pub(crate) fn find_generic(generics: &syn::Generics, some_ty: &syn::Type) -> Option<syn::Ident> {
+    let mut finder = FindParams {
+        params: generics.type_params().map(|ty| &ty.ident).collect(),
+        found: vec![],
+    };
+    finder.visit_type(some_ty);
+    if finder.found.len() == 1 {
+        finder.found.pop()
+    } else {
+        None
+    }
+}
+
+/// Whether the generic parameter `param` occurs in `ty`
+pub(crate) fn mentions(ty: &syn::Type, param: &syn::Ident) -> bool {
+    let mut finder = FindParams {
+        params: vec![param],
+        found: vec![],
+    };
+    finder.visit_type(ty);
+    !finder.found.is_empty()
+}
+
+/// Whether `predicate` is of the form `ty: Bounds`, in which case it is part of `bounds_of(ty)`
+fn is_bound_of(predicate: &syn::WherePredicate, ty: &syn::Ident) -> bool {
+    match predicate {
+        syn::WherePredicate::Type(syn::PredicateType {
+            lifetimes: None,
+            bounded_ty: syn::Type::Path(p),
+            ..
+        }) => p.qself.is_none() && p.path.is_ident(ty),
+        _ => false,
+    }
+}
+
+/// All bounds on the generic parameter `ty`, both inline and in the where clause
+pub(crate) fn bounds_of(generics: &syn::Generics, ty: &syn::Ident) -> Bounds {
+    let mut bounds = Bounds::new();
+    for param in generics.type_params() {
+        if param.ident == *ty {
+            bounds.extend(param.bounds.iter().cloned());
+        }
+    }
+    if let Some(where_clause) = generics.where_clause.as_ref() {
+        for clause in where_clause.predicates.iter() {
+            if let syn::WherePredicate::Type(predicate) = clause {
+                if is_bound_of(clause, ty) {
+                    bounds.extend(predicate.bounds.iter().cloned());
+                }
+            }
+        }
+    }
+    bounds
+}
+
+/// Removes all bounds on the generic parameter `ty` from `generics`, both inline and in the where
+/// clause. Used when the bounds of `ty` are added again for different types
+pub(crate) fn remove_bounds_of(generics: &mut syn::Generics, ty: &syn::Ident) {
+    for param in generics.type_params_mut() {
+        if param.ident == *ty {
+            param.bounds.clear();
+            param.colon_token = None;
+        }
+    }
+    if let Some(where_clause) = generics.where_clause.as_mut() {
+        where_clause.predicates = std::mem::take(&mut where_clause.predicates)
+            .into_iter()
+            .filter(|predicate| !is_bound_of(predicate, ty))
+            .collect();
+    }
+}
+
+/// All where predicates that mention the generic parameter `ty` and are not part of `bounds_of(ty)`,
+/// like `Vec<T>: Clone` or `for<'a> &'a T: IntoIterator`
+pub(crate) fn predicates_of<'g>(
+    generics: &'g syn::Generics,
+    ty: &syn::Ident,
+) -> Vec<&'g syn::WherePredicate> {
+    let where_clause = match generics.where_clause.as_ref() {
+        Some(where_clause) => where_clause,
+        None => return vec![],
+    };
+    where_clause
+        .predicates
+        .iter()
+        .filter(|predicate| !is_bound_of(predicate, ty))
+        .filter(|predicate| {
+            let mut finder = FindParams {
+                params: vec![ty],
+                found: vec![],
+            };
+            finder.visit_where_predicate(predicate);
+            !finder.found.is_empty()
+        })
+        .collect()
+}
+
+/// Returns a copy of `predicate` with every occurrence of the generic type `from` replaced by `to`
+pub(crate) fn substitute_predicate(
+    predicate: &syn::WherePredicate,
+    from: &syn::Ident,
+    to: &syn::Ident,
+) -> syn::WherePredicate {
+    let mut predicate = predicate.clone();
+    Substitute { from, to }.visit_where_predicate_mut(&mut predicate);
+    predicate
+}
+
+/// Returns a copy of `ty` with every occurrence of the generic type `from` replaced by `to`.
+///
+/// This also replaces `from` in paths like `from::Assoc` or `<from as Trait>::Assoc`.
+pub(crate) fn substitute(ty: &syn::Type, from: &syn::Ident, to: &syn::Ident) -> syn::Type {
+    let mut ty = ty.clone();
+    Substitute { from, to }.visit_type_mut(&mut ty);
+    ty
+}
+
+/// The generic parameter that a type path starts with, if it is one of `params`
+fn param_of<'p>(path: &syn::TypePath, params: &[&'p syn::Ident]) -> Option<&'p syn::Ident> {
+    if path.qself.is_some() || path.path.leading_colon.is_some() {
+        return None;
+    }
+    let first = path.path.segments.first()?;
+    if !first.arguments.is_empty() {
+        return None;
+    }
+    params.iter().find(|param| first.ident == ***param).copied()
+}
+
+/// Collects all generic parameters that occur in a type
+struct FindParams<'p> {
+    params: Vec<&'p syn::Ident>,
+    found: Vec<syn::Ident>,
+}
+
+impl<'ast, 'p> Visit<'ast> for FindParams<'p> {
+    fn visit_type_path(&mut self, path: &'ast syn::TypePath) {
+        if let Some(param) = param_of(path, &self.params) {
+            if !self.found.contains(param) {
+                self.found.push(param.clone());
+            }
+        }
+        syn::visit::visit_type_path(self, path);
+    }
+}
+
+/// Replaces all occurrences of the generic parameter `from` with `to`
+struct Substitute<'a> {
+    from: &'a syn::Ident,
+    to: &'a syn::Ident,
+}
+
+impl<'a> VisitMut for Substitute<'a> {
+    fn visit_type_path_mut(&mut self, path: &mut syn::TypePath) {
+        if param_of(path, &[self.from]).is_some() {
+            path.path.segments[0].ident = self.to.clone();
+        }
+        syn::visit_mut::visit_type_path_mut(self, path);
+    }
+}
@@ -18,8 +18,15 @@
 
 //! TODO: doc
 
+mod attributes;
+mod bool_like;
 mod error;
+mod generics;
+mod result_like;
+mod tri_state;
+use attributes::{Attributes, VariantMarker};
 use error::*;
+use generics::*;
 
 use convert_case::{Case, Casing};
 use proc_macro::TokenStream as TokenStream1;
@@ -40,6 +47,72 @@ mod sections {
     pub mod s06_entry_ops;
     pub mod s07_misc;
     pub mod s99_additions;
+
+    /// The groups of methods that are added to the enum, which can be selected with
+    /// `#[optional(only(...))]` and `#[optional(skip(...))]`
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub(crate) enum Section {
+        Querying,
+        RefAdapters,
+        GetContained,
+        Transformers,
+        BoolOps,
+        Iters,
+        EntryOps,
+        Misc,
+        Additions,
+    }
+
+    impl Section {
+        pub const ALL: [Section; 9] = [
+            Section::Querying,
+            Section::RefAdapters,
+            Section::GetContained,
+            Section::Transformers,
+            Section::BoolOps,
+            Section::Iters,
+            Section::EntryOps,
+            Section::Misc,
+            Section::Additions,
+        ];
+
+        /// The sections that are generated with `#[optional(some = Variant)]`
+        pub const BOOL_LIKE: [Section; 3] =
+            [Section::Querying, Section::Transformers, Section::BoolOps];
+
+        /// The name of the section as used in the attributes
+        pub fn name(self) -> &'static str {
+            match self {
+                Section::Querying => "querying",
+                Section::RefAdapters => "ref_adapters",
+                Section::GetContained => "get_contained",
+                Section::Transformers => "transformers",
+                Section::BoolOps => "bool_ops",
+                Section::Iters => "iters",
+                Section::EntryOps => "entry_ops",
+                Section::Misc => "misc",
+                Section::Additions => "additions",
+            }
+        }
+
+        pub fn from_name(name: &str) -> Option<Self> {
+            Self::ALL.iter().copied().find(|s| s.name() == name)
+        }
+
+        pub fn add_section(self, container: &DataContainer, impl_block: &mut TokenStream) {
+            match self {
+                Section::Querying => s01_querying::add_section(container, impl_block),
+                Section::RefAdapters => s02_ref_adapters::add_section(container, impl_block),
+                Section::GetContained => s03_get_contained::add_section(container, impl_block),
+                Section::Transformers => s04_transformers::add_section(container, impl_block),
+                Section::BoolOps => s05_bool_ops::add_section(container, impl_block),
+                Section::Iters => s05_iters::add_section(container, impl_block),
+                Section::EntryOps => s06_entry_ops::add_section(container, impl_block),
+                Section::Misc => s07_misc::add_section(container, impl_block),
+                Section::Additions => s99_additions::add_section(container, impl_block),
+            }
+        }
+    }
 }
 
 mod external {
@@ -49,10 +122,90 @@ mod external {
 
     pub mod impls;
     pub mod traits;
+
+    /// The trait implementations that can be disabled with `#[optional(no_impl(...))]`
+    #[derive(Clone, Copy, PartialEq, Eq)]
+    pub(crate) enum TraitImpl {
+        Default,
+        From,
+        IntoIterator,
+        /// The generated `<Name>Transpose` extension trait
+        Transpose,
+        /// Only implemented with `#[optional(some = Variant)]`
+        Not,
+    }
+
+    impl TraitImpl {
+        pub const ALL: [TraitImpl; 5] = [
+            TraitImpl::Default,
+            TraitImpl::From,
+            TraitImpl::IntoIterator,
+            TraitImpl::Transpose,
+            TraitImpl::Not,
+        ];
+
+        /// The name of the trait as used in the attributes
+        pub fn name(self) -> &'static str {
+            match self {
+                TraitImpl::Default => "Default",
+                TraitImpl::From => "From",
+                TraitImpl::IntoIterator => "IntoIterator",
+                TraitImpl::Transpose => "Transpose",
+                TraitImpl::Not => "Not",
+            }
+        }
+
+        pub fn from_name(name: &str) -> Option<Self> {
+            Self::ALL.iter().copied().find(|t| t.name() == name)
+        }
+
+        /// The trait implementations that are generated for `#[<path>(...)]` and can be disabled.
+        /// `bool_like` is whether `#[optional(some = Variant)]` is used
+        pub fn available(path: &str, bool_like: bool) -> &'static [TraitImpl] {
+            match (path, bool_like) {
+                ("result_like", _) => &[TraitImpl::From],
+                ("tri_state", _) => &[TraitImpl::Default, TraitImpl::From],
+                (_, true) => &[TraitImpl::Default, TraitImpl::From, TraitImpl::Not],
+                (_, false) => &[
+                    TraitImpl::Default,
+                    TraitImpl::From,
+                    TraitImpl::IntoIterator,
+                    TraitImpl::Transpose,
+                ],
+            }
+        }
+    }
 }
 
 type Bounds = syn::punctuated::Punctuated<syn::TypeParamBound, syn::token::Plus>;
 
+/// A `None`-like variant
+pub(crate) struct NoneVariant {
+    /// The name of the variant
+    ident: syn::Ident,
+    /// The fields of the variant, which are filled with `Default::default()` when it is created
+    /// from nothing
+    fields: syn::Fields,
+}
+
+/// A `Some`-like variant
+pub(crate) struct SomeVariant {
+    /// The name of the variant
+    ident: syn::Ident,
+    /// The fields of the variant
+    fields: Vec<SomeField>,
+}
+
+/// A field of a `Some`-like variant
+pub(crate) struct SomeField {
+    /// The name or index of the field
+    member: syn::Member,
+    /// The type of the field
+    ty: syn::Type,
+    /// Whether the field is marked with `#[optional(skip)]` and filled with `Default::default()`
+    skip: bool,
+}
+
 pub(crate) struct DataContainer {
     /// The name of the enum
     name: syn::Ident,
@@ -60,10 +213,14 @@ pub(crate) struct DataContainer {
     full_name: TokenStream,
     /// String representation of full_name
     full_name_string: String,
-    /// #name :: #some_variant
+    /// #name :: #some_variant. If there are several `Some`-like variants, this is the first one,
+    /// which is used to create new `Some`-like values
     some: TokenStream,
-    /// #name :: #none_variant
+    /// #name :: #none_variant, with any data in it filled in. If there are several `None`-like
+    /// variants, this is the one marked with `#[optional(default)]`
     none: TokenStream,
+    /// A pattern that matches all `None`-like variants, ignoring any data in them
+    none_pat: TokenStream,
     /// Name of #some_variant
     some_name: String,
     /// Name of #none_variant
@@ -72,37 +229,742 @@ pub(crate) struct DataContainer {
     some_name_snake: String,
     /// Name of #none_variant in snake_case
     none_name_snake: String,
-    /// Type inside #some_variant
+    /// All `Some`-like variants, starting with #some_variant
+    some_variants: Vec<SomeVariant>,
+    /// All `None`-like variants, including #none_variant
+    none_variants: Vec<NoneVariant>,
+    /// Type inside #some_variant. A tuple of all fields that aren't skipped if there are several
     some_ty: syn::Type,
     /// Name of #some_ty
     some_ty_name: String,
-    /// Whether the enum is generic
+    /// Whether the enum is generic over #some_ty, meaning #some_ty is exactly #generic,
+    /// #generic only has simple bounds and doesn't occur in the data of `None`-like variants
     is_generic: bool,
+    /// The generic parameter that occurs in #some_ty and is changed by methods like `map`, if any
+    generic: Option<syn::Ident>,
     /// The traits on the generic type. Empty if not generic
     bounds: Option<Bounds>,
-    /// impl #ty_generics
+    /// All generic parameters of the enum
+    generics: syn::Generics,
+    /// impl #impl_generics
     imp: TokenStream,
+    /// The where clause of the enum, for #imp blocks
+    imp_where: TokenStream,
 
-    /// #inline pub fn
+    /// Visibility of the methods in the current section
+    vis: syn::Visibility,
+    /// #inline #vis fn
     func: TokenStream,
-    /// #inline pub const fn
+    /// #inline #vis const fn with `#[optional(const)]`, otherwise the same as `func`
     c_func: TokenStream,
-    /// ::std::option::Option
+    /// ::core::option::Option
     opt: TokenStream,
+    /// Path to the `derive_optional` crate, for any generated references to it
+    krate: syn::Path,
+
+    /// The options set with `#[optional(...)]`
+    attrs: Attributes,
 }
 
 impl DataContainer {
+    /// Collects everything about the enum that the sections need. `some_variants` and
+    /// `none_variants` are the variants as split by `split_variants`
+    fn new(
+        name: syn::Ident,
+        vis: syn::Visibility,
+        generics: syn::Generics,
+        attrs: Attributes,
+        some_variants: Vec<syn::Variant>,
+        none_variants: Vec<syn::Variant>,
+        default_index: usize,
+    ) -> Result<Self> {
+        let none_variant = &none_variants[default_index];
+
+        // new `Some`-like values are created with the first `Some`-like variant
+        let some_ident = some_variants[0].ident.clone();
+        let none_ident = none_variant.ident.clone();
+        let some = quote! {#name::#some_ident};
+        let none = none_with_defaults(&name, &none_ident, &none_variant.fields);
+        let none_pat = {
+            let idents = none_variants.iter().map(|variant| &variant.ident);
+            quote! { #(#name::#idents { .. })|* }
+        };
+        let some_name = some_ident.to_string();
+        let none_name = none_ident.to_string();
+        let some_name_snake = some_name.to_case(Case::Snake);
+        let none_name_snake = none_name.to_case(Case::Snake);
+        let none_variants = none_variants
+            .into_iter()
+            .map(|variant| NoneVariant {
+                ident: variant.ident,
+                fields: variant.fields,
+            })
+            .collect::<Vec<_>>();
+
+        let mut error = Error::builder();
+        let some_variants = some_variants
+            .into_iter()
+            .map(|variant| {
+                let mut fields = vec![];
+                for (i, field) in variant.fields.into_iter().enumerate() {
+                    let skip = match attributes::parse_field_skip(&field.attrs, attrs.path) {
+                        Ok(skip) => skip,
+                        Err(e) => {
+                            error.push(e.into());
+                            false
+                        }
+                    };
+                    let member = match field.ident {
+                        Some(ident) => syn::Member::Named(ident),
+                        None => syn::Member::Unnamed(i.into()),
+                    };
+                    fields.push(SomeField {
+                        member,
+                        ty: field.ty,
+                        skip,
+                    });
+                }
+                SomeVariant {
+                    ident: variant.ident,
+                    fields,
+                }
+            })
+            .collect::<Vec<_>>();
+        error.ok_or_build()?;
+
+        let payload_of = |variant: &SomeVariant| {
+            variant
+                .fields
+                .iter()
+                .filter(|f| !f.skip)
+                .map(|f| f.ty.clone())
+                .collect::<Vec<_>>()
+        };
+        let payload = payload_of(&some_variants[0]);
+        let some_ty: syn::Type = match payload.as_slice() {
+            [] => {
+                let kind = if attrs.path == "result_like" {
+                    "Ok"
+                } else {
+                    "Some"
+                };
+                let msg = format!(
+                    "The `{}`-like variant needs at least one field that isn't skipped",
+                    kind
+                );
+                return Error::err_spanned(some_ident, msg);
+            }
+            [ty] => ty.clone(),
+            _ => syn::parse_quote! { (#(#payload),*) },
+        };
+        let mut error = Error::builder();
+        for variant in some_variants.iter().skip(1) {
+            let other = payload_of(variant);
+            if quote! {#(#other),*}.to_string() != quote! {#(#payload),*}.to_string() {
+                let msg = format!(
+                    "All `Some`-like variants need to contain the same types as `{}`",
+                    some_name
+                );
+                error.with_spanned(&variant.ident, msg);
+            }
+        }
+        error.ok_or_build()?;
+        let some_ty_name = some_ty.to_token_stream().to_string();
+
+        let generic = find_generic(&generics, &some_ty);
+        // methods like `as_ref` can't change the generic type in arbitrary where predicates or in
+        // the data of `None`-like variants
+        let is_generic = generic.as_ref().map_or(false, |g| {
+            *g == some_ty_name
+                && predicates_of(&generics, g).is_empty()
+                && !none_variants
+                    .iter()
+                    .flat_map(|variant| variant.fields.iter())
+                    .any(|field| !is_phantom_data(&field.ty) && mentions(&field.ty, g))
+        });
+        let bounds = generic
+            .as_ref()
+            .map(|generic| bounds_of(&generics, generic))
+            .filter(|bounds| !bounds.is_empty());
+
+        let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+        let imp = quote! {impl #impl_generics};
+        let full_name = quote! {#name #ty_generics};
+        let imp_where = where_clause.to_token_stream();
+        let full_name_string = full_name.to_string().replace(' ', "");
+
+        let opt = quote! {::core::option::Option};
+        let func = quote! {#[inline] #vis fn};
+        let c_func = func.clone(); // set in set_visibility
+
+        Ok(DataContainer {
+            name,
+            full_name,
+            full_name_string,
+            some,
+            none,
+            none_pat,
+            some_name,
+            none_name,
+            some_name_snake,
+            none_name_snake,
+            some_variants,
+            none_variants,
+            some_ty,
+            some_ty_name,
+            is_generic,
+            generic,
+            bounds,
+            generics,
+            imp,
+            imp_where,
+
+            vis,
+            func,
+            c_func,
+            opt,
+            krate: attrs.krate.clone(),
+
+            attrs,
+        })
+    }
+    fn set_visibility(&mut self, vis: syn::Visibility) {
+        self.func = quote! {#[inline] #vis fn};
+        self.c_func = if self.is_const() {
+            quote! {#[inline] #vis const fn}
+        } else {
+            self.func.clone()
+        };
+        self.vis = vis;
+    }
+    /// Whether the (C) methods are `const fn`. Skipped fields and data in `None`-like variants are
+    /// filled with `Default::default()` and have to be dropped, neither of which is const
+    fn is_const(&self) -> bool {
+        let has_defaults = self.some_fields().iter().any(|f| f.skip)
+            || self.none_variants.iter().any(|v| !is_unit_like(&v.fields));
+        self.attrs.const_fn && !has_defaults
+    }
+    /// The identifier of the generated method `key`, after applying `rename` and `rename_all`
+    fn method(&self, key: &str) -> syn::Ident {
+        syn::Ident::new(
+            &self.attrs.method_name(key, key),
+            proc_macro2::Span::call_site(),
+        )
+    }
+    /// #name with the generic type replaced by `ty`, for methods that change the contained type
+    fn name_with(&self, ty: impl ToTokens) -> TokenStream {
+        match self.generic.as_ref() {
+            Some(generic) => self.name_replacing(generic, ty),
+            None => self.full_name.clone(),
+        }
+    }
+    /// #name with the generic parameter `generic` replaced by `ty`
+    fn name_replacing(&self, generic: &syn::Ident, ty: impl ToTokens) -> TokenStream {
+        let name = &self.name;
+        let args = self.generics.params.iter().map(|param| match param {
+            syn::GenericParam::Type(t) if t.ident == *generic => ty.to_token_stream(),
+            syn::GenericParam::Type(t) => t.ident.to_token_stream(),
+            syn::GenericParam::Lifetime(l) => l.lifetime.to_token_stream(),
+            syn::GenericParam::Const(c) => c.ident.to_token_stream(),
+        });
+        quote! { #name<#(#args),*> }
+    }
+    /// The fields of #some_variant
+    fn some_fields(&self) -> &[SomeField] {
+        &self.some_variants[0].fields
+    }
+    /// Whether #some_ty is made up of several fields, so there is no `&#some_ty` to borrow
+    fn is_composite(&self) -> bool {
+        self.some_fields().iter().filter(|f| !f.skip).count() > 1
+    }
+    /// The names that `some_pat(name)` binds the fields that aren't skipped to
+    fn some_names(&self, name: &str) -> Vec<syn::Ident> {
+        if !self.is_composite() {
+            return vec![syn::Ident::new(name, proc_macro2::Span::call_site())];
+        }
+        let count = self.some_fields().iter().filter(|f| !f.skip).count();
+        (0..count)
+            .map(|i| quote::format_ident!("{}_{}", name, i))
+            .collect()
+    }
+    /// The contained value after matching `some_pat(name)`, like `x` or `(x_0, x_1)`
+    fn some_value(&self, name: &str) -> TokenStream {
+        let names = self.some_names(name);
+        if self.is_composite() {
+            quote! { (#(#names),*) }
+        } else {
+            quote! { #(#names)* }
+        }
+    }
+    /// The type of `some_value` when matched by reference, like `&T` or `(&A, &B)`
+    fn some_ref_ty(&self, reference: TokenStream) -> TokenStream {
+        if self.is_composite() {
+            let tys = self.some_fields().iter().filter(|f| !f.skip).map(|f| &f.ty);
+            quote! { (#(#reference #tys),*) }
+        } else {
+            let ty = &self.some_ty;
+            quote! { #reference #ty }
+        }
+    }
+    /// A pattern for all `Some`-like variants that binds the contained value with `binding`, like
+    /// `x`, `ref x`, `ref mut x` or `_`. Use `some_value` to get the bound value.
+    fn some_pat(&self, binding: TokenStream) -> TokenStream {
+        let patterns = self
+            .some_variants
+            .iter()
+            .map(|variant| self.some_variant_pat(variant, binding.clone()));
+        quote! { #(#patterns)|* }
+    }
+    /// `some_pat` for a single `Some`-like variant
+    fn some_variant_pat(&self, variant: &SomeVariant, binding: TokenStream) -> TokenStream {
+        let mut tokens = binding.into_iter().collect::<Vec<_>>();
+        let name = tokens.pop().map(|t| t.to_string()).unwrap_or_default();
+        let mode = tokens.into_iter().collect::<TokenStream>();
+        let mut names = self.some_names(&name).into_iter();
+        let patterns = variant
+            .fields
+            .iter()
+            .map(|field| {
+                if field.skip || name == "_" {
+                    quote! { _ }
+                } else {
+                    let name = names.next();
+                    quote! { #mode #name }
+                }
+            })
+            .collect();
+        self.some_fields_with(variant, patterns)
+    }
+    /// An expression that creates #some_variant from `inner`, which has the type #some_ty.
+    /// Skipped fields are filled with `Default::default()`
+    fn some_with(&self, inner: impl ToTokens) -> TokenStream {
+        self.some_variant_with(&self.some_variants[0], inner)
+    }
+    /// `some_with` for a single `Some`-like variant
+    fn some_variant_with(&self, variant: &SomeVariant, inner: impl ToTokens) -> TokenStream {
+        let names = self.some_names("v");
+        let mut values = if self.is_composite() {
+            names
+                .iter()
+                .map(ToTokens::to_token_stream)
+                .collect::<Vec<_>>()
+        } else {
+            vec![inner.to_token_stream()]
+        }
+        .into_iter();
+        let fields = variant
+            .fields
+            .iter()
+            .map(|field| match field.skip {
+                true => quote! { ::core::default::Default::default() },
+                false => values.next().unwrap_or_default(),
+            })
+            .collect();
+        let some = self.some_fields_with(variant, fields);
+        if self.is_composite() {
+            quote! {{
+                let (#(#names),*) = #inner;
+                #some
+            }}
+        } else {
+            some
+        }
+    }
+    /// Match arms for every `Some`-like variant that bind the contained value with `binding`, with
+    /// `body(variant)` as the expression. Used by methods like `map` that keep the variant by
+    /// creating the result with `some_variant_with`
+    fn some_arms(
+        &self,
+        binding: TokenStream,
+        body: impl Fn(&SomeVariant) -> TokenStream,
+    ) -> TokenStream {
+        let arms = self.some_variants.iter().map(|variant| {
+            let pat = self.some_variant_pat(variant, binding.clone());
+            let body = body(variant);
+            quote! { #pat => #body, }
+        });
+        quote! { #(#arms)* }
+    }
+    /// A `Some`-like variant with `values` as the patterns or expressions of its fields
+    fn some_fields_with(&self, variant: &SomeVariant, values: Vec<TokenStream>) -> TokenStream {
+        let name = &self.name;
+        let ident = &variant.ident;
+        let named = variant
+            .fields
+            .iter()
+            .any(|f| matches!(f.member, syn::Member::Named(_)));
+        if named {
+            let members = variant.fields.iter().map(|f| &f.member);
+            quote! { #name::#ident { #(#members: #values),* } }
+        } else {
+            quote! { #name::#ident(#(#values),*) }
+        }
+    }
+    /// The field of #none_variant that holds a reason, which is its only field that isn't
+    /// `PhantomData`, if there is exactly one and no other `None`-like variant
+    fn reason_field(&self) -> Option<(syn::Member, &syn::Type)> {
+        let none_variant = match self.none_variants.as_slice() {
+            [none_variant] => none_variant,
+            _ => return None,
+        };
+        let mut fields = none_variant
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, field)| !is_phantom_data(&field.ty));
+        let (i, field) = fields.next()?;
+        if fields.next().is_some() {
+            return None;
+        }
+        let member = match field.ident.as_ref() {
+            Some(ident) => syn::Member::Named(ident.clone()),
+            None => syn::Member::Unnamed(i.into()),
+        };
+        Some((member, &field.ty))
+    }
+    /// A pattern for #none_variant that binds its reason field with `binding`, like `ref r`
+    fn reason_pat(&self, member: &syn::Member, binding: TokenStream) -> TokenStream {
+        let name = &self.name;
+        let none = syn::Ident::new(&self.none_name, name.span());
+        quote! { #name::#none { #member: #binding, .. } }
+    }
+    /// A pattern for every `None`-like variant that binds its data, and an expression that moves
+    /// the data into the same variant of a #name with a different contained type. Data whose type
+    /// mentions #generic can't be moved and is filled with `Default::default()`, which needs the
+    /// predicates from `none_defaults_for`
+    fn none_conversions(&self) -> Vec<(TokenStream, TokenStream)> {
+        let name = &self.name;
+        let moved = |field: &syn::Field| match self.generic.as_ref() {
+            Some(generic) => !mentions(&field.ty, generic),
+            None => true,
+        };
+        self.none_variants
+            .iter()
+            .map(|variant| {
+                let ident = &variant.ident;
+                let pat = variant_fields_with(name, ident, &variant.fields, |i, field| {
+                    match !is_phantom_data(&field.ty) && moved(field) {
+                        true => none_binding(i).into_token_stream(),
+                        false => quote! { _ },
+                    }
+                });
+                let expr = variant_fields_with(name, ident, &variant.fields, |i, field| {
+                    if is_phantom_data(&field.ty) {
+                        quote! { ::core::marker::PhantomData }
+                    } else if moved(field) {
+                        none_binding(i).into_token_stream()
+                    } else {
+                        quote! { ::core::default::Default::default() }
+                    }
+                });
+                (pat, expr)
+            })
+            .collect()
+    }
+    /// Match arms that map every `None`-like variant to itself, moving any data in it. Used by
+    /// methods that return a different type, to keep the `None`-like variant
+    fn none_arms(&self) -> TokenStream {
+        self.none_arms_wrapped(|none| none)
+    }
+    /// `none_arms` with every expression wrapped by `wrap`, like `Ok(#none)`
+    fn none_arms_wrapped(&self, wrap: impl Fn(TokenStream) -> TokenStream) -> TokenStream {
+        let arms = self.none_conversions().into_iter().map(|(pat, expr)| {
+            let expr = wrap(expr);
+            quote! { #pat => #expr, }
+        });
+        quote! { #(#arms)* }
+    }
+    /// `none_arms` for methods that take `&self` or `&mut self`, like `as_ref`. The data is
+    /// cloned, which needs the predicates from `none_clone_bounds`. Only used if #is_generic, so
+    /// the data never mentions #generic
+    fn none_ref_arms(&self) -> TokenStream {
+        let name = &self.name;
+        let arms =
+            self.none_variants.iter().map(|variant| {
+                let ident = &variant.ident;
+                let pat = variant_fields_with(name, ident, &variant.fields, |i, field| {
+                    match is_phantom_data(&field.ty) {
+                        true => quote! { _ },
+                        false => {
+                            let binding = none_binding(i);
+                            quote! { ref #binding }
+                        }
+                    }
+                });
+                let expr = variant_fields_with(name, ident, &variant.fields, |i, field| {
+                    let ty = &field.ty;
+                    let binding = none_binding(i);
+                    match is_phantom_data(ty) {
+                        true => quote! { ::core::marker::PhantomData },
+                        false => quote! { <#ty as ::core::clone::Clone>::clone(#binding) },
+                    }
+                });
+                quote! { #pat => #expr, }
+            });
+        quote! { #(#arms)* }
+    }
+    /// The predicates that `none_conversions` needs for a #name with #generic replaced by `ty`
+    fn none_defaults_for(&self, ty: &syn::Ident) -> TokenStream {
+        let generic = match self.generic.as_ref() {
+            Some(generic) => generic,
+            None => return TokenStream::new(),
+        };
+        let types = self
+            .none_variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .filter(|field| !is_phantom_data(&field.ty) && mentions(&field.ty, generic))
+            .map(|field| substitute(&field.ty, generic, ty));
+        quote! { #(#types: ::core::default::Default,)* }
+    }
+    /// The predicates that `none_ref_arms` needs
+    fn none_clone_bounds(&self) -> TokenStream {
+        self.none_data_bounds(&self.none_variants, quote! { ::core::clone::Clone })
+    }
+    /// The predicates that #none needs, for methods that create a `None`-like value from nothing,
+    /// like `take` or `Default`
+    fn none_default_bounds(&self) -> TokenStream {
+        let none_variant = self
+            .none_variants
+            .iter()
+            .find(|variant| variant.ident == self.none_name);
+        self.none_data_bounds(none_variant, quote! { ::core::default::Default })
+    }
+    /// `#ty: #bound` for the data in `variants`. The predicates are higher-ranked, which keeps
+    /// the compiler from rejecting them right away if the data has a concrete type that doesn't
+    /// implement `bound`. Only the methods that need them can't be called then
+    fn none_data_bounds<'v>(
+        &self,
+        variants: impl IntoIterator<Item = &'v NoneVariant>,
+        bound: TokenStream,
+    ) -> TokenStream {
+        let l = self.lifetime("x");
+        let types = variants
+            .into_iter()
+            .flat_map(|variant| variant.fields.iter())
+            .filter(|field| !is_phantom_data(&field.ty))
+            .map(|field| &field.ty);
+        quote! { #(for<#l> #types: #bound,)* }
+    }
+    /// The where clause of the enum with `predicates` added, for trait implementations that
+    /// need more than the enum itself
+    fn imp_where_with(&self, predicates: TokenStream) -> TokenStream {
+        if predicates.is_empty() {
+            return self.imp_where.clone();
+        }
+        let existing = self
+            .generics
+            .where_clause
+            .iter()
+            .flat_map(|where_clause| where_clause.predicates.iter());
+        quote! { where #(#existing,)* #predicates }
+    }
+    /// #some_ty with #generic replaced by `ty`, for methods that change the contained type
+    fn some_ty_with(&self, ty: &syn::Ident) -> syn::Type {
+        match self.generic.as_ref() {
+            Some(generic) => substitute(&self.some_ty, generic, ty),
+            None => self.some_ty.clone(),
+        }
+    }
+    /// An identifier for a generic parameter of a generated method, like the `U` in `map<U>`,
+    /// that doesn't clash with the generic parameters of the enum
+    fn param(&self, name: &str) -> syn::Ident {
+        fresh_param(&self.generics, name)
+    }
+    /// A lifetime for a generated method, like the `'a` in `as_ref<'a>`, that doesn't clash with
+    /// the lifetimes of the enum
+    fn lifetime(&self, name: &str) -> syn::Lifetime {
+        let is_taken = |candidate: &str| {
+            self.generics
+                .lifetimes()
+                .any(|l| l.lifetime.ident == candidate)
+        };
+        let mut ident = name.to_owned();
+        let mut i = 2;
+        while is_taken(&ident) {
+            ident = format!("{}{}", name, i);
+            i += 1;
+        }
+        syn::Lifetime::new(&format!("'{}", ident), proc_macro2::Span::call_site())
+    }
     fn where_clause_for(&self, ty: impl ToTokens) -> Option<TokenStream> {
         self.bounds_for(ty).map(|b| quote! { where #b })
     }
     fn bounds_for(&self, ty: impl ToTokens) -> Option<TokenStream> {
         self.bounds.as_ref().map(|b| quote! { #ty: #b, })
     }
+    /// The where predicates of the enum that mention #generic, with #generic replaced by `ty`
+    fn predicates_for(&self, ty: &syn::Ident) -> TokenStream {
+        let generic = match self.generic.as_ref() {
+            Some(generic) => generic,
+            None => return TokenStream::new(),
+        };
+        let predicates = predicates_of(&self.generics, generic)
+            .into_iter()
+            .map(|predicate| substitute_predicate(predicate, generic, ty));
+        quote! { #(#predicates,)* }
+    }
 }
 
 /// TODO: doc
 /// TODO: talk about `T` as placeholder for the contained type
 ///
+/// ## Variants
+///
+/// The enum needs a `Some`-like variant that contains a value and a `None`-like variant without
+/// one. The value can be in a tuple variant or in a struct-like
+/// variant with a single named field:
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional, Debug, PartialEq)]
+/// enum Lookup<T> {
+///     Present { value: T },
+///     Absent,
+/// }
+///
+/// let found = Lookup::Present { value: 2 }.map(|x| x * 2);
+/// assert_eq!(found, Lookup::Present { value: 4 });
+/// ```
+///
+/// If the `Some`-like variant has several fields, the contained value is the tuple of all of
+/// them. Fields marked with `#[optional(skip)]` are not part of the contained value. They are
+/// ignored when reading the value and filled with `Default::default()` when creating a new one:
+///
+/// ```
+/// # use derive_optional::Optional;
+/// use std::marker::PhantomData;
+///
+/// #[derive(Optional, Debug, PartialEq)]
+/// enum Span<Unit> {
+///     Range(usize, usize, #[optional(skip)] PhantomData<Unit>),
+///     Empty,
+/// }
+///
+/// let span: Span<u8> = Span::from((1, 3));
+/// assert_eq!(span.map_or(0, |(start, end)| end - start), 2);
+/// ```
+///
+/// Since there is no single value to borrow in that case, methods that return a reference to the
+/// contained value are not added for several fields that aren't skipped. This includes the (G)
+/// methods, `as_slice`, `as_mut_slice`, `inspect`, `contains` and the
+/// [entry-like operations](#entry-like-operations-to-insert-a-value-and-return-a-reference-entry_ops).
+/// Methods like `iter` and `as_option_ref` instead return a tuple of references, like
+/// `(&A, &B)`.
+///
+/// The `None`-like variant can be a unit variant, an empty variant like `None()` or `None {}`, or
+/// contain only `PhantomData` fields, for generic types that aren't used anywhere else.
+///
+/// Which variant is which can also be marked explicitly with `#[optional(some)]` or
+/// `#[optional(none)]`. This allows the `None`-like variant to carry other data as well. Methods
+/// that keep the `None`-like variant, like `map` or `and`, move the data into the result, and
+/// methods on references like `as_ref` clone it. Only a new `None`-like value, like in `Default`,
+/// `take` or `filter`, is filled with `Default::default()`, so these methods can't be called if
+/// the data doesn't implement `Default`:
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional, Debug, PartialEq)]
+/// enum Lookup<T> {
+///     #[optional(some)]
+///     Found(T),
+///     NotFound { reason: String },
+/// }
+///
+/// let missing: Lookup<u8> = Lookup::NotFound { reason: "no such key".to_owned() };
+/// assert_eq!(missing.unwrap_or(0), 0);
+/// assert_eq!(Lookup::<u8>::default(), Lookup::NotFound { reason: String::new() });
+///
+/// let missing: Lookup<u8> = Lookup::NotFound { reason: "no such key".to_owned() };
+/// assert_eq!(missing.map(|x| x * 2), Lookup::NotFound { reason: "no such key".to_owned() });
+/// ```
+///
+/// Data whose type mentions the generic type that (M) methods change, like `Vec<T>`, is filled
+/// with `Default::default()` by them as well, and the (G) methods are not added in that case.
+///
+/// There can also be several `None`-like variants. All variants except for the `Some`-like one
+/// are `None`-like then, and one of them has to be marked with `#[optional(default)]`. That one
+/// is used whenever a new `None`-like value is created, like in `Default` or `take`. Methods that
+/// return a different type, like `map` or `as_ref`, keep the `None`-like variant. Instead of
+/// `is_<none>`, there is an `is_<variant>` method for every `None`-like variant:
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional, Debug, PartialEq)]
+/// enum Remote<T> {
+///     Loaded(T),
+///     Loading,
+///     #[optional(default)]
+///     NotAsked,
+///     Failed,
+/// }
+///
+/// let loading: Remote<u8> = Remote::Loading;
+/// assert!(loading.is_loading());
+/// assert_eq!(loading.map(|x| x * 2), Remote::Loading);
+/// assert_eq!(Remote::Loaded(1).filter(|x| *x > 1), Remote::NotAsked);
+/// assert_eq!(Remote::<u8>::default(), Remote::NotAsked);
+/// ```
+///
+/// Several variants that contain the same types can be marked as `Some`-like with
+/// `#[optional(some)]`. Methods like `unwrap`, `filter` or `iter` work on all of them, and methods
+/// that return a different type, like `map`, keep the `Some`-like variant. New `Some`-like values,
+/// like in `From<T>` or `insert`, use the first one. Like with several `None`-like variants, there
+/// is an `is_<variant>` method for every `Some`-like variant:
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional, Debug, PartialEq)]
+/// enum Value<T> {
+///     #[optional(some)]
+///     Cached(T),
+///     #[optional(some)]
+///     Fresh(T),
+///     Absent,
+/// }
+///
+/// let fresh = Value::Fresh(2);
+/// assert!(fresh.is_fresh());
+/// assert_eq!(fresh.as_option_ref(), Some(&2));
+/// assert_eq!(fresh.map(|x| x * 2), Value::Fresh(4));
+/// assert_eq!(Value::from(1), Value::Cached(1));
+/// ```
+///
+/// An enum with two unit variants has no contained value, but can be used like `Option<()>` or
+/// `bool` with `#[optional(some = Variant)]`. Instead of the methods below, this adds
+/// `is_<some>` and `is_<none>` (`querying`), `then` and `then_some` (`transformers`) and `and`,
+/// `or` and `xor` (`bool_ops`), as well as `From<bool>`, `From<Self> for bool`, `Not` and
+/// `Default`:
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional, Debug, PartialEq)]
+/// #[optional(some = Shown)]
+/// enum Visibility {
+///     Hidden,
+///     Shown,
+/// }
+///
+/// let visibility = Visibility::from(true);
+/// assert!(visibility.is_shown());
+/// assert_eq!(visibility.then_some(1), Some(1));
+/// assert_eq!(!Visibility::Shown, Visibility::Hidden);
+/// assert_eq!(Visibility::Hidden.or(Visibility::Shown), Visibility::Shown);
+/// assert!(!bool::from(Visibility::default()));
+/// ```
+///
+/// Options for methods, sections and traits that are not generated this way are rejected:
+///
+/// ```compile_fail
+/// # use derive_optional::Optional;
+/// #[derive(Optional)]
+/// #[optional(some = Shown, unwrap_msg = "hidden", no_impl(IntoIterator))]
+/// enum Visibility {
+///     Hidden,
+///     Shown,
+/// }
+/// ```
+///
 /// ## Generics
 ///
 /// `derive(Optional)` can be done on types with or without generics:
@@ -127,114 +989,448 @@ impl DataContainer {
 /// like `map` or `as_ref` need to change the contained type. See [Added Methods](#added-methods)
 /// for a list of functions that are affected by this.
 ///
-/// **Important: Not all forms of generics and trait bounds are fully supported.**
+/// Any number of generic types, lifetimes and const generics can be used. Only a generic type that
+/// occurs in the `Some`-like variant is changed by methods like `map`, all others are passed
+/// through unchanged:
+///
+/// ```
+/// # use derive_optional::Optional;
+/// use std::collections::HashSet;
+/// use std::hash::BuildHasher;
+///
+/// #[derive(Optional)]
+/// enum Handle<S: BuildHasher> {
+///     Set(HashSet<u32, S>),
+///     Empty,
+/// }
+///
+/// #[derive(Optional)]
+/// enum Borrowed<'a, T> {
+///     Ref(&'a [T]),
+///     Empty,
+/// }
+///
+/// #[derive(Optional)]
+/// enum Slot<T, const N: usize> {
+///     Filled([T; N]),
+///     Empty,
+/// }
+/// ```
+///
+/// Which methods are added depends on how the generic type occurs in the `Some`-like variant:
+///
+/// - If the contained type is exactly the generic type, like `T` in `WithGenerics`, all methods
+///   are added.
+/// - If exactly one generic type occurs somewhere in the contained type, like `T` in `Vec<T>` or
+///   `&'a [T]`, the methods marked with (M) in [Added Methods](#added-methods) change every
+///   occurrence of it. The methods marked with (G) are not added, since they need to wrap the
+///   contained type itself, like `as_ref` turning `T` into `&T`.
+/// - If no generic type or several generic types occur in the contained type, like in `(K, V)`,
+///   the contained type is never changed, since derive_optional can't tell which generic type is
+///   meant. (M) methods keep the contained type as it is and (G) methods are not added.
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional, Debug, PartialEq)]
+/// enum Batch<T> {
+///     Items(Vec<T>),
+///     Empty,
+/// }
+///
+/// let lengths = Batch::Items(vec!["a", "bc"]).map(|v| v.iter().map(|s| s.len()).collect());
+/// assert_eq!(lengths, Batch::Items(vec![1, 2]));
+/// ```
+///
+/// Any where clause is kept on the generated `impl` blocks. Where predicates that mention the
+/// changed generic type, other than simple bounds like `T: Clone`, are copied onto the (M)
+/// methods with the generic type replaced, so `map` on the enum below requires
+/// `for<'a> &'a U: IntoIterator`. Since these predicates can't be adjusted for a contained
+/// type like `&T`, the (G) methods are not added in that case:
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional)]
+/// enum Iterable<T>
+/// where
+///     for<'a> &'a T: IntoIterator,
+/// {
+///     Has(T),
+///     Nothing,
+/// }
+///
+/// let set = Iterable::Has(vec![1, 2]).map(|v| v.into_iter().collect::<std::collections::BTreeSet<_>>());
+/// assert!(set.is_has());
+/// ```
+///
+/// ## `no_std`
+///
+/// The generated code only uses items from `core`, so `derive(Optional)` can be used in
+/// `#![no_std]` crates without any additional configuration.
+///
+/// ## Attributes
+///
+/// The generated code can be configured with `#[optional(...)]` attributes on the enum.
+///
+/// #### Selecting sections
+///
+/// The added methods are grouped into sections, as listed in [Added Methods](#added-methods).
+/// `#[optional(only(...))]` generates only the listed sections, while `#[optional(skip(...))]`
+/// generates everything except the listed sections:
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional)]
+/// #[optional(only(querying, get_contained))]
+/// enum OnlySome {
+///     Value(usize),
+///     Nothing,
+/// }
+/// assert_eq!(OnlySome::Value(1).unwrap(), 1);
+///
+/// #[derive(Optional)]
+/// #[optional(skip(entry_ops, misc))]
+/// enum SkipSome {
+///     Value(usize),
+///     Nothing,
+/// }
+/// impl SkipSome {
+///     // no conflict, because `misc` is skipped
+///     pub fn take(&mut self) -> usize {
+///         42
+///     }
+/// }
+/// ```
+///
+/// `only` and `skip` cannot be combined.
+///
+/// ```compile_fail
+/// # use derive_optional::Optional;
+/// #[derive(Optional)]
+/// #[optional(only(querying), skip(misc))]
+/// enum Both {
+///     Value(usize),
+///     Nothing,
+/// }
+/// ```
+///
+/// #### Disabling trait implementations
+///
+/// `#[optional(no_impl(...))]` disables the listed implementations from [Traits](#traits), so that
+/// they can be implemented or derived manually. Possible values are `Default`, `From` (all
+/// three `From` conversions), `IntoIterator`, `Transpose` (the `<Name>Transpose` trait and
+/// the `transpose` methods on `Self<Result<T, E>>` and `Self<Option<T>>`) and `Not` (only
+/// implemented with [`#[optional(some = Variant)]`](#variants)).
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional, Default)]
+/// #[optional(no_impl(Default, From))]
+/// enum CustomTraits {
+///     Value(usize),
+///     #[default]
+///     Nothing,
+/// }
+/// impl From<usize> for CustomTraits {
+///     fn from(x: usize) -> Self {
+///         if x == 0 { CustomTraits::Nothing } else { CustomTraits::Value(x) }
+///     }
+/// }
+/// ```
+///
+/// Since a derived `Default` on an enum requires a `#[default]` variant, forgetting to disable
+/// the generated `Default` is detected:
+///
+/// ```compile_fail
+/// # use derive_optional::Optional;
+/// #[derive(Optional, Default)]
+/// enum Conflicting {
+///     Value(usize),
+///     #[default]
+///     Nothing,
+/// }
+/// ```
+///
+/// `Not` is only implemented with `some = Variant`, so it can't be disabled otherwise:
+///
+/// ```compile_fail
+/// # use derive_optional::Optional;
+/// #[derive(Optional)]
+/// #[optional(no_impl(Not))]
+/// enum Unary {
+///     Value(usize),
+///     Nothing,
+/// }
+/// ```
+///
+/// #### Renaming methods
+///
+/// `#[optional(rename(method = "new_name", ...))]` changes the name of individual methods. Methods
+/// are referred to by their name in `Option`, so the `is_<some>` and `is_<none>` methods are
+/// `is_some` and `is_none` respectively. `#[optional(rename_all(prefix = "...", suffix = "..."))]`
+/// adds a prefix and/or suffix to all methods that are not explicitly renamed.
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional)]
+/// #[optional(rename(is_some = "has_body", unwrap = "into_body"), rename_all(suffix = "_body"))]
+/// enum HTTPBody {
+///     Data(Vec<u8>),
+///     Empty,
+/// }
+/// let body = HTTPBody::Data(vec![1, 2, 3]);
+/// assert!(body.has_body());
+/// assert!(!body.is_empty_body());
+/// assert_eq!(body.as_slice_body(), &[vec![1, 2, 3]]);
+/// assert_eq!(body.into_body(), vec![1, 2, 3]);
+/// ```
 ///
-/// Generics should be limited to only one generic type, and traits are only allowed on
-/// the type itself, either directly or in a where clause, like in the example above.  
-/// The following would be rejected:
+/// Renaming a method that is not generated for the enum is an error:
 ///
 /// ```compile_fail
-/// # use std::convert::TryFrom;
 /// # use derive_optional::Optional;
 /// #[derive(Optional)]
-/// enum TooManyGenerics<A, B> where A: TryFrom<B> {
-///     SomeVariant(<A as TryFrom<B>>::Error),
-///     NoneVariant,
+/// #[optional(skip(get_contained), rename(unwrap = "get"))]
+/// enum Skipped {
+///     Value(usize),
+///     Nothing,
 /// }
 /// ```
 ///
-/// If derive_optional would want to add all methods, it would need to convert generics. Take e.g. the `map` function.
-/// It would need to change the contained type from `<A as TryFrom<B>>::Error` to some other type. Would that be
-/// `<U as TryFrom<B>>::Error`? Or would an alternative to `B` need to be introduced as well, so `<U as TryFrom<V>>::Error`?
-/// Not only is it not clear even in this relatively simple example, it would also be a herculean task to find all
-/// instances of the generic types in the arbitrary type-expressions, since procedural macros don't have type information.
-/// It would need to rely on what is essentially string search-and-replace to change the types, which is not ideal.
+/// #### Visibility
 ///
-/// Thus, at least for now, some methods are simply not added if the generic types or traits are too complex. Please be aware
-/// that the detection for this is not ideal, so there may be errors in both directions where the methods aren't added in
-/// places where they could be, or they are added and you get weird compiler errors.
+/// The generated methods have the same visibility as the enum itself. This can be changed with
+/// `#[optional(vis = "...")]` for all methods, or `#[optional(vis(section = "...", ...))]` for
+/// individual sections, where an empty string makes the methods private.
+///
+/// ```
+/// mod inner {
+///     # use derive_optional::Optional;
+///     #[derive(Optional)]
+///     #[optional(vis = "pub(crate)", vis(entry_ops = ""))]
+///     pub enum Narrowed {
+///         Value(usize),
+///         Nothing,
+///     }
+/// }
+/// assert_eq!(inner::Narrowed::Value(1).unwrap(), 1);
+/// ```
+///
+/// ```compile_fail
+/// mod inner {
+///     # use derive_optional::Optional;
+///     #[derive(Optional)]
+///     #[optional(vis = "pub(crate)", vis(entry_ops = ""))]
+///     pub enum Narrowed {
+///         Value(usize),
+///         Nothing,
+///     }
+/// }
+/// inner::Narrowed::Nothing.insert(1); // private
+/// ```
+///
+/// #### Panic message
+///
+/// `#[optional(unwrap_msg = "...")]` replaces the message that `unwrap` panics with, which is
+/// ``called `<Name>::unwrap()` on a `<None>` value`` by default, with the `None`-like variant that
+/// was found.
+///
+/// ```should_panic
+/// # use derive_optional::Optional;
+/// #[derive(Optional)]
+/// #[optional(unwrap_msg = "no change was recorded")]
+/// enum HasChanged {
+///     Changed(usize),
+///     NoChange,
+/// }
+/// HasChanged::NoChange.unwrap(); // panics with "no change was recorded"
+/// ```
+///
+/// Like with `Option`, the panicking methods are `#[track_caller]`, so the panic points to the
+/// place where e.g. `unwrap` was called.
+///
+/// #### `const` methods
+///
+/// `#[optional(const)]` makes all methods `const fn` that can be on stable Rust. This requires
+/// Rust 1.83 or newer, which is why it is not the default. The affected methods are marked with
+/// (C) in [Added Methods](#added-methods). Since `Default::default()` can't be called in a
+/// `const fn`, this has no effect if the `Some`-like variant has
+/// [skipped fields](#variants) or the `None`-like variant carries data other than `PhantomData`.
+/// `replace` is not `const` if the `Some`-like variant has several fields, since the tuple that it
+/// receives has to be taken apart.
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional)]
+/// #[optional(const)]
+/// enum Constant<T> {
+///     Value(T),
+///     Nothing,
+/// }
+/// const VALUE: Constant<u8> = Constant::Value(1);
+/// const IS_VALUE: bool = VALUE.is_value();
+/// const INNER: u8 = VALUE.unwrap();
+/// # assert!(IS_VALUE);
+/// # assert_eq!(INNER, 1);
+/// ```
+///
+/// #### Crate path
+///
+/// `#[optional(crate = "path")]` sets the path under which `derive_optional` is available, in
+/// case it is re-exported from another crate and not a direct dependency. Any references to
+/// this crate in the generated code go through that path, so it has to resolve where the enum is
+/// declared. The default is `::derive_optional`.
+///
+/// ```
+/// mod prelude {
+///     pub use derive_optional::Optional;
+/// }
+///
+/// #[derive(prelude::Optional)]
+/// #[optional(crate = "crate::prelude")]
+/// enum ReExported {
+///     Value(usize),
+///     Nothing,
+/// }
+/// # fn main() {}
+/// ```
+///
+/// ```compile_fail
+/// # use derive_optional::Optional;
+/// #[derive(Optional)]
+/// #[optional(crate = "crate::missing")]
+/// enum Misplaced {
+///     Value(usize),
+///     Nothing,
+/// }
+/// # fn main() {}
+/// ```
 ///
 /// ## Added Methods
 ///
 /// Symbols:
 /// - (G): only added if the enum is generic over the contained type, as described in [Generics](#generics)
+/// - (M): changes the generic type if one occurs in the contained type, as described in [Generics](#generics)
 /// - (U): not yet added because it's unstable
+/// - (C): `const fn` with [`#[optional(const)]`](#const-methods)
 ///
-/// #### Querying the contained values
-/// - `is_<some>` (where `<some>` is the snake_case version of the `Some`-like variant)
+/// #### Querying the contained values (`querying`)
+/// - `is_<some>` (C) (where `<some>` is the snake_case version of the `Some`-like variant, or one
+///   `is_<variant>` for each of [several `Some`-like variants](#variants))
 /// - `is_<some>_and`
-/// - `is_<none>` (same as above, but for the `None`-like variant)
+/// - `is_<none>` (C) (same as above, but for the `None`-like variant, or one `is_<variant>` for
+///   each of [several `None`-like variants](#variants))
 /// - ~~`is_<none>_or`~~ (U)
 ///
-/// #### Adapter for working with references
-/// - `as_ref` (G)
-/// - `as_mut` (G)
+/// #### Adapter for working with references (`ref_adapters`)
+/// - `as_ref` (G, C)
+/// - `as_mut` (G, C)
 /// - `as_pin_ref` (G)
 /// - `as_pin_mut` (G)
-/// - `as_slice`
-/// - `as_mut_slice`
+/// - `as_slice` (C)
+/// - `as_mut_slice` (C)
 ///
-/// #### Getting to contained values
-/// - `expect`
-/// - `unwrap`
+/// #### Getting to contained values (`get_contained`)
+/// - `expect` (C)
+/// - `unwrap` (C)
 /// - `unwrap_or`
 /// - `unwrap_or_else`
 /// - `unwrap_or_default`
-/// - `unwrap_unchecked`
+/// - `unwrap_unchecked` (C)
 ///
-/// #### Transforming contained values
-/// - `map` (G)
+/// #### Transforming contained values (`transformers`)
+/// - `map` (M, only added if a generic type occurs in the contained type)
 /// - `inspect`
-/// - `map_or` (G)
-/// - `map_or_else` (G)
+/// - `map_or`
+/// - `map_or_else`
 /// - `ok_or`
 /// - `ok_or_else`
 /// - `as_deref` (G)
 /// - `as_deref_mut` (G)
 ///
-/// #### Iterator constructors
+/// #### Iterator constructors (`iters`)
 /// - `iter`
 /// - `iter_mut`
 ///
-/// #### Boolean operations on the values, eager and lazy
-/// - `and`
-/// - `and_then`
+/// #### Boolean operations on the values, eager and lazy (`bool_ops`)
+/// - `and` (M)
+/// - `and_then` (M)
 /// - `filter`
 /// - `or`
 /// - `or_else`
 /// - `xor`
 ///
-/// #### Entry-like operations to insert a value and return a reference
+/// #### Entry-like operations to insert a value and return a reference (`entry_ops`)
 /// - `insert`
 /// - `get_or_insert`
 /// - ~~`get_or_insert_default`~~ (U)
 /// - `get_or_insert_with`
 ///
-/// #### Misc
-/// - `take`
+/// #### Misc (`misc`)
+/// - `take` (C)
 /// - `take_if`
-/// - `replace`
+/// - `replace` (C)
 /// - `contains`
 /// - `zip` (G)
-/// - `zip_with`
+/// - `zip_with` (M)
 /// - `unzip` (G)
 ///
-/// ## Additional Methods not in `Option`
+/// ## Additional Methods not in `Option` (`additions`)
 /// - `as_option_ref`: Converts `&Self` to `Option<&inner>`, similar to `as_ref`
-///   but swapping `Self` with `Option`
+///   but swapping `Self` with `Option` (C)
 /// - `as_option_mut`: Converts `&mut Self` to `Option<&mut inner>`, similar to `as_mut`
-///   but swapping `Self` with `Option`
+///   but swapping `Self` with `Option` (C)
+///
+/// If there is a single `None`-like variant and it carries a reason, meaning exactly one field
+/// that isn't `PhantomData` (see [Variants](#variants)), these are added as well:
+/// - `reason`: Returns `Option<&reason>`
+/// - `reason_mut`: Returns `Option<&mut reason>`
+/// - `into_reason`: Returns `Option<reason>`
+/// - `unwrap_or_else_reason`: Like `unwrap_or_else`, but the closure receives the reason
+/// - `ok_or_reason`: Converts `Self` to `Result<inner, reason>`
+///
+/// #### Methods on references and nested types (G)
+/// These are part of `transformers`, but added in separate `impl` blocks on `Self<&T>` and
+/// `Self<&mut T>`:
+/// - `copied`: Converts to `Self<T>` if `T: Copy`
+/// - `cloned`: Converts to `Self<T>` if `T: Clone`
+///
+/// The same goes for `Self<Result<T, E>>` and `Self<Option<T>>`:
+/// - `transpose`: Converts to `Result<Self<T>, E>` or `Option<Self<T>>`. Not added with
+///   `no_impl(Transpose)`, like the `<Name>Transpose` trait below
 ///
 /// ## Traits
 /// - `From<T> for Self`
 /// - `From<Option<T>> for Self`
 /// - `From<Self> for Option<T>`
 /// - `Self: Default`
+/// - `Self: IntoIterator`
+/// - `<Name>Transpose` (G): A new trait next to the enum with a `transpose` method for
+///   `Result<Self<T>, E>` and `Option<Self<T>>`, which converts them to `Self<Result<T, E>>` and
+///   `Self<Option<T>>`. It has to be in scope to be used:
+///
+/// ```
+/// # use derive_optional::Optional;
+/// #[derive(Optional, Debug, PartialEq)]
+/// enum Lookup<T> {
+///     Found(T),
+///     Missing,
+/// }
+///
+/// fn parse(lookup: Lookup<&str>) -> Result<Lookup<u8>, std::num::ParseIntError> {
+///     lookup.map(str::parse).transpose()
+/// }
+/// assert_eq!(parse(Lookup::Found("1")), Ok(Lookup::Found(1)));
+/// assert_eq!(parse(Lookup::Missing), Ok(Lookup::Missing));
+///
+/// // in another module, this would need `use path::to::LookupTranspose;`
+/// let result: Result<Lookup<u8>, ()> = Ok(Lookup::Found(1));
+/// assert_eq!(result.transpose(), Lookup::Found(Ok(1)));
+/// ```
 ///
 /// ## Things that were **not** added
 /// - unstable or nightly-only methods and traits
 ///   - this sadly includes the try (`?`) operator
-#[proc_macro_derive(Optional)]
+#[proc_macro_derive(Optional, attributes(optional))]
 pub fn optional(input: TokenStream1) -> TokenStream1 {
     let input = syn::parse_macro_input!(input as syn::DeriveInput);
     match optional_internal(input) {
@@ -243,7 +1439,155 @@ pub fn optional(input: TokenStream1) -> TokenStream1 {
     }
 }
 
+/// Adds the methods of `Result` to an enum with an `Ok`-like and an `Err`-like variant.
+///
+/// The first variant is the `Ok`-like variant and contains the value like the `Some`-like variant
+/// of [`Optional`](derive.Optional.html), so it can have several fields and
+/// `#[result_like(skip)]` fields. The second variant is the `Err`-like variant and needs exactly
+/// one field that isn't `PhantomData`, which is the error.
+///
+/// ```
+/// # use derive_optional::ResultLike;
+/// #[derive(ResultLike, Debug, PartialEq)]
+/// enum Response<T, E> {
+///     Accepted(T),
+///     Rejected { reason: E },
+/// }
+///
+/// let response: Response<u32, &str> = Response::Rejected { reason: "full" };
+/// assert_eq!(response.map_err(str::len), Response::Rejected { reason: 4 });
+///
+/// let response: Response<u32, &str> = Ok(3).into();
+/// assert_eq!(response.and_then(|x| Response::Accepted(x * 2)).ok(), Some(6));
+/// ```
+///
+/// The `#[result_like(...)]` attribute takes the same options as `#[optional(...)]`, except for
+/// the selection of sections. Only `From` can be disabled with `no_impl`, since the other traits
+/// are not implemented:
+///
+/// ```compile_fail
+/// # use derive_optional::ResultLike;
+/// #[derive(ResultLike)]
+/// #[result_like(no_impl(Default))]
+/// enum Response {
+///     Accepted(u8),
+///     Rejected(u8),
+/// }
+/// ```
+///
+/// ## Added Methods
+///
+/// - `is_ok`, `is_ok_and`, `is_err`, `is_err_and`. These are named after the variants, like
+///   `is_accepted` and `is_accepted_and`
+/// - `expect`, `unwrap`, `expect_err`, `unwrap_err`: Unlike `Result`, the panic message doesn't
+///   contain the value, so it doesn't need to implement `Debug`
+/// - `unwrap_or`, `unwrap_or_else`, `unwrap_or_default`
+/// - `ok`, `err`
+/// - `map`: If the value contains a generic parameter that doesn't occur in the error
+/// - `map_err`: If the error contains a generic parameter that doesn't occur in the value
+/// - `map_or`, `map_or_else`, `inspect`, `inspect_err`
+/// - `and`, `and_then`: Can change the type of the value like `map`
+/// - `or`, `or_else`: Can change the type of the error like `map_err`
+/// - `transpose`: On `Self<Option<T>>`, if the value is exactly a generic parameter without
+///   bounds that doesn't occur in the error
+///
+/// ## Traits
+/// - `From<Result<T, E>> for Self`
+/// - `From<Self> for Result<T, E>`
+#[proc_macro_derive(ResultLike, attributes(result_like))]
+pub fn result_like(input: TokenStream1) -> TokenStream1 {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match result_like::result_like_internal(input) {
+        Ok(s) => s.into(),
+        Err(e) => e.into(),
+    }
+}
+
+/// Adds methods for three states of a value to an enum with a `Defined`-like, a `Null`-like and
+/// an `Undefined`-like variant, like the fields of a JSON PATCH request that can be set, set to
+/// `null` or left out.
+///
+/// The variant with data is the `Defined`-like variant and contains the value like the
+/// `Some`-like variant of [`Optional`](derive.Optional.html). The other two variants can only
+/// contain `PhantomData` and have to be marked with `#[tri_state(null)]` for the `Null`-like
+/// variant and `#[tri_state(undefined)]` for the `Undefined`-like variant.
+///
+/// ```
+/// # use derive_optional::TriState;
+/// #[derive(TriState, Debug, PartialEq)]
+/// enum Patch<T> {
+///     Set(T),
+///     #[tri_state(null)]
+///     Clear,
+///     #[tri_state(undefined)]
+///     Keep,
+/// }
+///
+/// let mut name = Some("old");
+/// Patch::Keep.merge_into(&mut name);
+/// assert_eq!(name, Some("old"));
+/// Patch::Set("new").merge_into(&mut name);
+/// assert_eq!(name, Some("new"));
+/// Patch::Clear.merge_into(&mut name);
+/// assert_eq!(name, None);
+///
+/// assert_eq!(Patch::from(Some(None::<u8>)), Patch::Clear);
+/// assert_eq!(Patch::Set(1).map(|x| x + 1).into_option(), Some(2));
+/// ```
+///
+/// The markers can't be left out, since the names of the variants don't tell which one is which:
+///
+/// ```compile_fail
+/// # use derive_optional::TriState;
+/// #[derive(TriState)]
+/// enum Patch<T> {
+///     Set(T),
+///     Clear,
+///     Keep,
+/// }
+/// ```
+///
+/// The `#[tri_state(...)]` attribute takes the same options as `#[optional(...)]`, except for
+/// the selection of sections and `unwrap_msg`. `no_impl` can disable `Default` and `From`:
+///
+/// ```compile_fail
+/// # use derive_optional::TriState;
+/// #[derive(TriState)]
+/// #[tri_state(unwrap_msg = "not set")]
+/// enum Patch<T> {
+///     Set(T),
+///     #[tri_state(null)]
+///     Clear,
+///     #[tri_state(undefined)]
+///     Keep,
+/// }
+/// ```
+///
+/// ## Added Methods
+///
+/// - `is_defined`, `is_null`, `is_undefined`: Named after the variants, like `is_set`,
+///   `is_clear` and `is_keep`. These are `const fn` with `#[tri_state(const)]`
+/// - `into_option`: Converts `Self` to `Option<T>`, with `None` for both `Null` and `Undefined`
+/// - `map`: Changes the generic parameter in the value and keeps `Null` and `Undefined`. Only
+///   added if the value contains a generic parameter
+/// - `merge_into`: Applies `Self` to an `&mut Option<T>`. `Defined(x)` sets it to `Some(x)`,
+///   `Null` sets it to `None` and `Undefined` leaves it unchanged
+///
+/// ## Traits
+/// - `From<Option<Option<T>>> for Self`: `None` is `Undefined` and `Some(None)` is `Null`
+/// - `From<Self> for Option<Option<T>>`
+/// - `Self: Default`: Returns `Undefined`
+#[proc_macro_derive(TriState, attributes(tri_state))]
+pub fn tri_state(input: TokenStream1) -> TokenStream1 {
+    let input = syn::parse_macro_input!(input as syn::DeriveInput);
+    match tri_state::tri_state_internal(input) {
+        Ok(s) => s.into(),
+        Err(e) => e.into(),
+    }
+}
+
 fn optional_internal(input: syn::DeriveInput) -> Result<TokenStream> {
+    let attrs = Attributes::parse(&input.attrs, "optional")?;
     let name = input.ident;
 
     let data = match input.data {
@@ -254,127 +1598,73 @@ fn optional_internal(input: syn::DeriveInput) -> Result<TokenStream> {
     .map_err(|span| Error::new(span, "Optional can only be used on enums"))?;
 
     let variants = data.variants;
-    if variants.len() != 2 {
-        let msg = "Optional only works when there are exactly 2 enum variants";
-        return Error::err_spanned(variants, msg);
-    }
+    check_default_conflict(&input.attrs, &variants, &attrs, "Optional", "`None`-like")?;
 
-    let (some_variant, none_variant) = {
-        let mut iter = variants.into_iter();
-        let a = iter.next().unwrap(); // unwrap ok because we checked len == 2
-        let b = iter.next().unwrap();
-
-        let a_data = !matches!(a.fields, syn::Fields::Unit);
-        let b_data = !matches!(b.fields, syn::Fields::Unit);
-        match (a_data, b_data) {
-            (false, false) => {
-                let msg = "Optional needs exactly one variant with data (the `Some(T)` equivalent)";
-                return Error::builder()
-                    .with_spanned(a, msg)
-                    .with_spanned(b, msg)
-                    .build_err();
-            }
-            (true, true) => {
-                let msg = "Optional needs exactly one unit variant (the `None` equivalent)";
-                return Error::builder()
-                    .with_spanned(a, msg)
-                    .with_spanned(b, msg)
-                    .build_err();
-            }
-            (true, false) => (a, b),
-            (false, true) => (b, a),
-        }
-    };
-
-    let some_ident = some_variant.ident;
-    let none_ident = none_variant.ident;
-    let some = quote! {#name::#some_ident};
-    let none = quote! {#name::#none_ident};
-    let some_name = some_ident.to_string();
-    let none_name = none_ident.to_string();
-    let some_name_snake = some_name.to_case(Case::Snake);
-    let none_name_snake = none_name.to_case(Case::Snake);
-
-    if some_variant.fields.len() != 1 {
-        let msg = "Optional currently only supports one type in the variant";
-        return Error::err_spanned(some_variant.fields, msg);
-    }
-    let some_field = some_variant.fields.into_iter().next().unwrap();
-    if let Some(ident) = some_field.ident.as_ref() {
-        let msg = "Optional currently only supports tuple variants for the `Some` variant";
-        return Error::err_spanned(ident, msg);
-    }
-    let some_ty = some_field.ty;
-    let some_ty_name = some_ty.to_token_stream().to_string();
-
-    let (is_generic, bounds, imp, full_name);
-    if let Some(in_bounds) = check_generics(input.generics, &some_ty_name)? {
-        is_generic = true;
-        if !in_bounds.is_empty() {
-            imp = quote! {impl<#some_ty: #in_bounds>};
-            bounds = Some(in_bounds);
-        } else {
-            imp = quote! {impl<#some_ty>};
-            bounds = None;
-        };
-        full_name = quote! {#name<#some_ty>};
-    } else {
-        is_generic = false;
-        bounds = None;
-        imp = quote! {impl};
-        full_name = quote! {#name};
+    if let Some(some) = attrs.some.clone() {
+        return bool_like::bool_like_internal(
+            name,
+            input.vis,
+            input.generics,
+            attrs,
+            variants,
+            some,
+        );
+    }
+    if variants.len() < 2 {
+        let msg = "Optional needs at least 2 enum variants";
+        return Error::err_spanned(variants, msg);
     }
-    let full_name_string = full_name.to_string().replace(' ', "");
 
-    let opt = quote! {::std::option::Option};
-    let func = quote! {#[inline] pub fn};
-    let c_func = quote! {#[inline] pub const fn};
+    let (some_variants, none_variants, default_index) = split_variants(variants)?;
 
-    let container = DataContainer {
+    let vis = input.vis;
+    let mut container = DataContainer::new(
         name,
-        full_name,
-        full_name_string,
-        some,
-        none,
-        some_name,
-        none_name,
-        some_name_snake,
-        none_name_snake,
-        some_ty,
-        some_ty_name,
-        is_generic,
-        bounds,
-        imp,
-
-        func,
-        c_func,
-        opt,
-    };
+        vis.clone(),
+        input.generics,
+        attrs,
+        some_variants,
+        none_variants,
+        default_index,
+    )?;
 
     let mut impl_block = TokenStream::new();
     let mut additional_impls = TokenStream::new();
 
-    sections::s99_additions::add_section(&container, &mut impl_block);
-    sections::s05_bool_ops::add_section(&container, &mut impl_block);
-    sections::s06_entry_ops::add_section(&container, &mut impl_block);
-    sections::s03_get_contained::add_section(&container, &mut impl_block);
-    sections::s05_iters::add_section(&container, &mut impl_block);
-    sections::s07_misc::add_section(&container, &mut impl_block);
-    sections::s01_querying::add_section(&container, &mut impl_block);
-    sections::s02_ref_adapters::add_section(&container, &mut impl_block);
-    sections::s04_transformers::add_section(&container, &mut impl_block);
+    for section in container.attrs.sections.clone() {
+        let section_vis = container.attrs.visibility(section).unwrap_or(&vis).clone();
+        container.set_visibility(section_vis);
+        section.add_section(&container, &mut impl_block);
+    }
 
+    let impls_vis = container.attrs.vis().unwrap_or(&vis).clone();
+    container.set_visibility(impls_vis);
     external::traits::add_external(&container, &mut additional_impls);
     external::impls::add_external(&container, &mut additional_impls);
 
-    let DataContainer { full_name, imp, .. } = container;
+    container.attrs.check_unused_renames()?;
+
+    let DataContainer {
+        full_name,
+        imp,
+        imp_where,
+        krate,
+        ..
+    } = container;
 
     let tokens = quote! {
-        #imp #full_name {
+        #[allow(dead_code)]
+        #imp #full_name #imp_where {
             #impl_block
         }
 
         #additional_impls
+
+        // fails to compile if the path set with `crate = "..."` does not resolve
+        const _: () = {
+            #[allow(unused_imports)]
+            use #krate as _;
+        };
     };
 
     // println!("//////////////////////////////////////////////////");
@@ -386,56 +1676,245 @@ fn optional_internal(input: syn::DeriveInput) -> Result<TokenStream> {
     Ok(tokens)
 }
 
-fn check_generics(generics: syn::Generics, some_ty_name: &str) -> Result<Option<Bounds>> {
-    let mut generic_type = None;
-    let mut out_bounds = Bounds::new();
+/// Splits the variants into the `Some`-like variants and the `None`-like variants, and returns the
+/// index of the `None`-like variant that is used to create new `None`-like values.
+///
+/// The `Some`-like variants are the ones marked with `#[optional(some)]`, or otherwise the only
+/// unmarked variant with data. All other variants are `None`-like.
+fn split_variants(
+    variants: syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+) -> Result<(Vec<syn::Variant>, Vec<syn::Variant>, usize)> {
     let mut error = Error::builder();
-    for ty in generics.params.into_iter() {
-        match ty {
-            syn::GenericParam::Type(ty) => {
-                if generic_type.is_some() {
-                    let msg = "Optional currently only supports one generic type";
-                    error.with_spanned(ty, msg);
-                } else {
-                    generic_type = Some(ty.ident);
-                    out_bounds.extend(ty.bounds);
+    let markers = variants
+        .iter()
+        .map(|variant| {
+            attributes::parse_variant_marker(&variant.attrs).unwrap_or_else(|e| {
+                error.push(e.into());
+                None
+            })
+        })
+        .collect::<Vec<_>>();
+    error.ok_or_build()?;
+
+    let marked = |marker: VariantMarker| {
+        (0..variants.len())
+            .filter(|&i| markers[i] == Some(marker))
+            .collect::<Vec<_>>()
+    };
+    let mut some_indices = marked(VariantMarker::Some);
+    if some_indices.is_empty() {
+        let unmarked = (0..variants.len())
+            .filter(|&i| markers[i].is_none())
+            .collect::<Vec<_>>();
+        let with_data = unmarked
+            .iter()
+            .copied()
+            .filter(|&i| !is_unit_like(&variants[i].fields))
+            .collect::<Vec<_>>();
+        match (with_data.as_slice(), unmarked.as_slice()) {
+            ([index], _) | ([], [index]) => some_indices.push(*index),
+            ([], _) => {
+                let msg = "Optional needs exactly one variant with data (the `Some(T)` \
+                           equivalent). Use `#[optional(some)]` to mark the `Some`-like variant, \
+                           or `#[optional(some = Variant)]` for two unit variants";
+                let mut error = Error::builder();
+                for variant in variants.iter() {
+                    error.with_spanned(variant, msg);
                 }
+                return error.build_err();
             }
             _ => {
-                let msg = "Optional currently only supports a generic type";
-                error.with_spanned(ty, msg);
+                let msg = "Optional needs exactly one variant with data (the `Some(T)` \
+                           equivalent). Use `#[optional(some)]` to mark several `Some`-like \
+                           variants or `#[optional(none)]` to mark `None`-like variants with \
+                           data. For an `Ok`/`Err` pair, use `#[derive(ResultLike)]` instead";
+                let mut error = Error::builder();
+                for &i in with_data.iter() {
+                    error.with_spanned(&variants[i], msg);
+                }
+                return error.build_err();
             }
         }
     }
+    if some_indices.len() == variants.len() {
+        let msg = "Optional needs at least one `None`-like variant";
+        return Error::err_spanned(variants, msg);
+    }
 
-    if let Some(where_clause) = generics.where_clause {
-        for clause in where_clause.predicates.into_iter() {
-            match clause {
-                syn::WherePredicate::Type(syn::PredicateType {
-                    bounded_ty: syn::Type::Path(p),
-                    bounds,
-                    ..
-                }) if p.path.is_ident(some_ty_name) => {
-                    out_bounds.extend(bounds);
-                }
-                _ => {
-                    let msg = "Optional currently only supports where clauses on the generic type";
-                    error.with_spanned(clause, msg);
-                }
+    let none_indices = (0..variants.len())
+        .filter(|i| !some_indices.contains(i))
+        .collect::<Vec<_>>();
+    let marked_default = marked(VariantMarker::Default);
+    let default_index = match marked_default.as_slice() {
+        [index] => none_indices.iter().position(|i| i == index).unwrap_or(0),
+        [] if none_indices.len() == 1 => 0,
+        [] => {
+            let msg = "Several `None`-like variants need one of them to be marked with \
+                       `#[optional(default)]`, which is used to create new `None`-like values";
+            let mut error = Error::builder();
+            for &i in none_indices.iter() {
+                error.with_spanned(&variants[i].ident, msg);
             }
+            return error.build_err();
         }
+        _ => {
+            let msg = "Only one variant can be marked with `#[optional(default)]`";
+            let mut error = Error::builder();
+            for &i in marked_default.iter() {
+                error.with_spanned(&variants[i].ident, msg);
+            }
+            return error.build_err();
+        }
+    };
+
+    let (some_variants, none_variants) = variants
+        .into_iter()
+        .enumerate()
+        .partition::<Vec<_>, _>(|(i, _)| some_indices.contains(i));
+    let without_index = |variants: Vec<(usize, syn::Variant)>| {
+        variants.into_iter().map(|(_, v)| v).collect::<Vec<_>>()
+    };
+    Ok((
+        without_index(some_variants),
+        without_index(none_variants),
+        default_index,
+    ))
+}
+
+/// Checks for a `#[default]` variant or a `#[derive(Default)]` after `#[derive(Optional)]`, which
+/// would conflict with the generated `Default` implementation. `derive` and `variant` describe
+/// the derive and the variant it uses as the default, like `Optional` and `None`-like.
+///
+/// A `Default` in the same `derive` as `Optional` cannot be detected, but deriving `Default` on an
+/// enum always requires a `#[default]` variant.
+fn check_default_conflict(
+    input_attrs: &[syn::Attribute],
+    variants: &syn::punctuated::Punctuated<syn::Variant, syn::token::Comma>,
+    attrs: &Attributes,
+    derive: &str,
+    variant: &str,
+) -> Result<()> {
+    if !attrs.implements(external::TraitImpl::Default) {
+        return Ok(());
     }
+    let msg = format!(
+        "{} implements `Default` as the {} variant, which conflicts with this. \
+         Add `#[{}(no_impl(Default))]` to use a different implementation",
+        derive, variant, attrs.path
+    );
 
-    error.ok_or_build()?;
+    let mut error = Error::builder();
+    for attr in input_attrs {
+        if !attr.path().is_ident("derive") {
+            continue;
+        }
+        let paths = attr.parse_args_with(
+            syn::punctuated::Punctuated::<syn::Path, syn::token::Comma>::parse_terminated,
+        )?;
+        for path in paths {
+            if path.segments.last().map_or(false, |s| s.ident == "Default") {
+                error.with_spanned(path, &msg);
+            }
+        }
+    }
+    for variant in variants {
+        for attr in &variant.attrs {
+            if attr.path().is_ident("default") {
+                error.with_spanned(attr, &msg);
+            }
+        }
+    }
+    error.ok_or_build()
+}
+
+/// An identifier for a generic parameter of a generated method, like the `U` in `map<U>`,
+/// that doesn't clash with `generics`
+fn fresh_param(generics: &syn::Generics, name: &str) -> syn::Ident {
+    let is_taken = |candidate: &str| {
+        generics.params.iter().any(|param| match param {
+            syn::GenericParam::Type(t) => t.ident == candidate,
+            syn::GenericParam::Const(c) => c.ident == candidate,
+            syn::GenericParam::Lifetime(_) => false,
+        })
+    };
+    let mut ident = name.to_owned();
+    let mut i = 2;
+    while is_taken(&ident) {
+        ident = format!("{}{}", name, i);
+        i += 1;
+    }
+    syn::Ident::new(&ident, proc_macro2::Span::call_site())
+}
 
-    if let Some(ty) = generic_type {
-        if ty != *some_ty_name {
-            let msg = "The generic type must be the same as the type in the `Some` variant";
-            return Error::err_spanned(ty, msg);
+/// Whether a variant with these fields is treated as the `None`-like variant without a marker,
+/// meaning it has no fields or only `PhantomData` fields
+fn is_unit_like(fields: &syn::Fields) -> bool {
+    fields.iter().all(|field| is_phantom_data(&field.ty))
+}
+
+/// Whether `ty` is a path to `PhantomData`, like `PhantomData<T>` or `core::marker::PhantomData<T>`
+fn is_phantom_data(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(p) if p.qself.is_none() => p
+            .path
+            .segments
+            .last()
+            .map_or(false, |segment| segment.ident == "PhantomData"),
+        _ => false,
+    }
+}
+
+/// The `None`-like variant with all its fields filled in. `PhantomData` fields are filled with the
+/// unit struct itself to keep this const, everything else with `Default::default()`
+fn none_with_defaults(name: &syn::Ident, ident: &syn::Ident, fields: &syn::Fields) -> TokenStream {
+    variant_filled_with(
+        name,
+        ident,
+        fields,
+        quote! { ::core::default::Default::default() },
+    )
+}
+
+/// A variant with all its `PhantomData` fields filled with the unit struct and all other fields
+/// with `value`
+fn variant_filled_with(
+    name: &syn::Ident,
+    ident: &syn::Ident,
+    fields: &syn::Fields,
+    value: TokenStream,
+) -> TokenStream {
+    variant_fields_with(name, ident, fields, |_, field| {
+        if is_phantom_data(&field.ty) {
+            quote! { ::core::marker::PhantomData }
+        } else {
+            value.clone()
         }
+    })
+}
 
-        Ok(Some(out_bounds))
-    } else {
-        Ok(None)
+/// A variant with `value(index, field)` as the pattern or expression of every field
+fn variant_fields_with(
+    name: &syn::Ident,
+    ident: &syn::Ident,
+    fields: &syn::Fields,
+    value: impl Fn(usize, &syn::Field) -> TokenStream,
+) -> TokenStream {
+    let values = fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| value(i, field))
+        .collect::<Vec<_>>();
+    match fields {
+        syn::Fields::Unit => quote! { #name::#ident },
+        syn::Fields::Unnamed(_) => quote! { #name::#ident(#(#values),*) },
+        syn::Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| &field.ident);
+            quote! { #name::#ident { #(#names: #values),* } }
+        }
     }
 }
+
+/// The binding for the field at `index` of a `None`-like variant in `none_conversions`
+fn none_binding(index: usize) -> syn::Ident {
+    syn::Ident::new(&format!("none_{}", index), proc_macro2::Span::call_site())
+}
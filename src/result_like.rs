@@ -0,0 +1,160 @@
+//! `#[derive(ResultLike)]`, which reuses the `DataContainer` of `Optional`: The `Ok`-like variant
+//! is the `Some`-like variant and the `Err`-like variant is a `None`-like variant that carries the
+//! error as its reason.
+
+use super::*;
+use external::TraitImpl;
+
+mod impls;
+mod s01_querying;
+mod s02_get_contained;
+mod s03_transformers;
+mod s04_bool_ops;
+mod traits;
+
+pub(crate) fn result_like_internal(input: syn::DeriveInput) -> Result<TokenStream> {
+    let attrs = Attributes::parse(&input.attrs, "result_like")?;
+    let name = input.ident;
+
+    let data = match input.data {
+        syn::Data::Enum(data) => Ok(data),
+        syn::Data::Struct(data) => Err(data.struct_token.span),
+        syn::Data::Union(data) => Err(data.union_token.span),
+    }
+    .map_err(|span| Error::new(span, "ResultLike can only be used on enums"))?;
+
+    let variants = data.variants;
+    if variants.len() != 2 {
+        let msg = "ResultLike only works when there are exactly 2 enum variants";
+        return Error::err_spanned(variants, msg);
+    }
+    let mut iter = variants.into_iter();
+    let ok_variant = iter.next().unwrap(); // unwrap ok because we checked len == 2
+    let err_variant = iter.next().unwrap();
+
+    let mut error = Error::builder();
+    if ok_variant.fields.is_empty() {
+        let msg = "The `Ok`-like variant (the first one) needs to contain a value";
+        error.with_spanned(&ok_variant, msg);
+    }
+    let err_fields = err_variant
+        .fields
+        .iter()
+        .filter(|field| !is_phantom_data(&field.ty))
+        .count();
+    if err_fields != 1 {
+        let msg = "The `Err`-like variant (the second one) needs exactly one field that isn't \
+                   `PhantomData`";
+        error.with_spanned(&err_variant, msg);
+    }
+    error.ok_or_build()?;
+
+    let vis = input.vis;
+    let mut container = DataContainer::new(
+        name,
+        vis.clone(),
+        input.generics,
+        attrs,
+        vec![ok_variant],
+        vec![err_variant],
+        0,
+    )?;
+    let vis = container.attrs.vis().unwrap_or(&vis).clone();
+    // unlike `set_visibility`, the `Err`-like variant is never created without an error
+    let has_skipped = container.some_fields().iter().any(|f| f.skip);
+    container.func = quote! {#[inline] #vis fn};
+    container.c_func = if container.attrs.const_fn && !has_skipped {
+        quote! {#[inline] #vis const fn}
+    } else {
+        container.func.clone()
+    };
+    container.vis = vis;
+
+    let mut impl_block = TokenStream::new();
+    let mut additional_impls = TokenStream::new();
+
+    s01_querying::add_section(&container, &mut impl_block);
+    s02_get_contained::add_section(&container, &mut impl_block);
+    s03_transformers::add_section(&container, &mut impl_block);
+    s04_bool_ops::add_section(&container, &mut impl_block);
+    traits::add_external(&container, &mut additional_impls);
+    impls::add_external(&container, &mut additional_impls);
+
+    container.attrs.check_unused_renames()?;
+
+    let DataContainer {
+        full_name,
+        imp,
+        imp_where,
+        krate,
+        ..
+    } = container;
+
+    Ok(quote! {
+        #[allow(dead_code)]
+        #imp #full_name #imp_where {
+            #impl_block
+        }
+
+        #additional_impls
+
+        // fails to compile if the path set with `crate = "..."` does not resolve
+        const _: () = {
+            #[allow(unused_imports)]
+            use #krate as _;
+        };
+    })
+}
+
+impl DataContainer {
+    /// The type of the error in the `Err`-like variant
+    fn err_ty(&self) -> &syn::Type {
+        // checked in `result_like_internal`
+        self.reason_field()
+            .expect("the `Err`-like variant has an error")
+            .1
+    }
+    /// A pattern for the `Err`-like variant that binds the error with `binding`, like `e` or `ref e`
+    fn err_pat(&self, binding: TokenStream) -> TokenStream {
+        let (member, _) = self
+            .reason_field()
+            .expect("the `Err`-like variant has an error");
+        self.reason_pat(&member, binding)
+    }
+    /// An expression that creates the `Err`-like variant with `err` as the error
+    fn err_with(&self, err: impl ToTokens) -> TokenStream {
+        let variant = &self.none_variants[0];
+        variant_filled_with(
+            &self.name,
+            &variant.ident,
+            &variant.fields,
+            err.to_token_stream(),
+        )
+    }
+    /// The generic parameter that methods like `map` change, which has to occur in the `Ok`-like
+    /// value and not in the error
+    fn ok_generic(&self) -> Option<&syn::Ident> {
+        self.generic
+            .as_ref()
+            .filter(|generic| !mentions(self.err_ty(), generic))
+    }
+    /// The generic parameter that methods like `map_err` change, which has to occur in the error
+    /// and not in the `Ok`-like value
+    fn err_generic(&self) -> Option<syn::Ident> {
+        find_generic(&self.generics, self.err_ty())
+            .filter(|generic| !mentions(&self.some_ty, generic))
+    }
+    /// The bounds and where predicates of the enum on `generic`, with `generic` replaced by `ty`
+    fn predicates_replacing(&self, generic: &syn::Ident, ty: &syn::Ident) -> TokenStream {
+        let bounds = bounds_of(&self.generics, generic);
+        let bounds = if bounds.is_empty() {
+            None
+        } else {
+            Some(quote! { #ty: #bounds, })
+        };
+        let predicates = predicates_of(&self.generics, generic)
+            .into_iter()
+            .map(|predicate| substitute_predicate(predicate, generic, ty));
+        quote! { #bounds #(#predicates,)* }
+    }
+}
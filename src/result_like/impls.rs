@@ -0,0 +1,56 @@
+use super::*;
+
+pub(crate) fn add_external(container: &DataContainer, additional_impls: &mut TokenStream) {
+    #[allow(unused_variables)]
+    #[rustfmt::skip]
+    let DataContainer {
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
+    } = *container;
+
+    let err_e = container.err_pat(quote! {e});
+    let err_from_e = container.err_with(quote! {e});
+
+    // transpose on #name<Option<U>>
+    // the bounds of #generic would have to hold for `Option<U>`, so only without bounds
+    let transposable = container
+        .ok_generic()
+        .filter(|_| is_generic && bounds.is_none());
+    if let Some(generic) = transposable {
+        let u = container.param("U");
+        let transpose = container.method("transpose");
+        let doc = format!(
+            "Transposes a `{name}` of an `Option` into an `Option` of a `{name}`. Equivalent to `Result::transpose`.",
+            name = name,
+        );
+        let mut transpose_generics = generics.clone();
+        for param in transpose_generics.type_params_mut() {
+            if param.ident == *generic {
+                param.ident = u.clone();
+            }
+        }
+        let (imp, _, imp_where) = transpose_generics.split_for_impl();
+        let name_opt_u = container.name_with(quote! {#opt<#u>});
+        let name_u = container.name_with(&u);
+        let some_x = container.some_pat(quote! {x});
+        let some_from_x = container.some_with(quote! {x});
+        additional_impls.extend(quote! {
+            #[allow(dead_code)]
+            impl #imp #name_opt_u #imp_where {
+                #[doc = #doc]
+                #func #transpose(self) -> #opt<#name_u> {
+                    match self {
+                        #some_x => match x {
+                            #opt::Some(x) => #opt::Some(#some_from_x),
+                            #opt::None => #opt::None,
+                        },
+                        #err_e => #opt::Some(#err_from_e),
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,115 @@
+use super::*;
+use syn::Ident;
+
+pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStream) {
+    #[allow(unused_variables)]
+    #[rustfmt::skip]
+    let DataContainer {
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
+    } = *container;
+
+    let x = container.some_value("x");
+    let some_any = container.some_pat(quote! {_});
+    let some_x = container.some_pat(quote! {x});
+    let err_e = container.err_pat(quote! {e});
+    let err_ty = container.err_ty();
+    let f = container.param("F");
+
+    /////////////////////////////////////////////////////////////////////////
+    // Querying the contained values
+    /////////////////////////////////////////////////////////////////////////
+
+    // is_ok
+    let is_ok = Ident::new(
+        &attrs.method_name("is_ok", &format!("is_{}", some_name_snake)),
+        some.clone().into_iter().last().unwrap().span(),
+    );
+    {
+        let doc = format!(
+            "Returns `true` if the `{name}` is an `{ok}` value. Equivalent to `Result::is_ok`.",
+            name = name,
+            ok = some_name,
+        );
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #c_func #is_ok(&self) -> bool {
+                ::core::matches!(*self, #some_any)
+            }
+        });
+    }
+
+    // is_ok_and
+    {
+        let is_ok_and = Ident::new(
+            &attrs.method_name("is_ok_and", &format!("is_{}_and", some_name_snake)),
+            is_ok.span(),
+        );
+        let doc = format!(
+            "Returns `true` if the `{name}` is an `{ok}` value and the value inside of it matches a predicate. Equivalent to `Result::is_ok_and`.",
+            name = name,
+            ok = some_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #is_ok_and<#f>(self, f: #f) -> bool
+            where
+                #f: FnOnce(#some_ty) -> bool,
+            {
+                match self {
+                    #some_x => f(#x),
+                    _ => false,
+                }
+            }
+        });
+    }
+
+    // is_err
+    let is_err = Ident::new(
+        &attrs.method_name("is_err", &format!("is_{}", none_name_snake)),
+        none_variants[0].ident.span(),
+    );
+    {
+        let doc = format!(
+            "Returns `true` if the `{name}` is an `{err}` value. Equivalent to `Result::is_err`.",
+            name = name,
+            err = none_name,
+        );
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #c_func #is_err(&self) -> bool {
+                ::core::matches!(*self, #none_pat)
+            }
+        });
+    }
+
+    // is_err_and
+    {
+        let is_err_and = Ident::new(
+            &attrs.method_name("is_err_and", &format!("is_{}_and", none_name_snake)),
+            is_err.span(),
+        );
+        let doc = format!(
+            "Returns `true` if the `{name}` is an `{err}` value and the error inside of it matches a predicate. Equivalent to `Result::is_err_and`.",
+            name = name,
+            err = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #is_err_and<#f>(self, f: #f) -> bool
+            where
+                #f: FnOnce(#err_ty) -> bool,
+            {
+                match self {
+                    #err_e => f(e),
+                    _ => false,
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,232 @@
+use super::*;
+
+pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStream) {
+    #[allow(unused_variables)]
+    #[rustfmt::skip]
+    let DataContainer {
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
+    } = *container;
+
+    let x = container.some_value("x");
+    let some_x = container.some_pat(quote! {x});
+    let err_e = container.err_pat(quote! {e});
+    let err_ty = container.err_ty();
+    let f = container.param("F");
+
+    /////////////////////////////////////////////////////////////////////////
+    // Getting to contained values
+    /////////////////////////////////////////////////////////////////////////
+
+    // unlike `Result`, the panic messages don't include the value, so it doesn't need `Debug`
+
+    // expect
+    {
+        let expect = container.method("expect");
+        let doc = format!(
+            "Returns the contained `{ok}` value, consuming `self`. Equivalent to `Result::expect`.
+
+# Panics
+
+Panics if the value is an `{err}` with a custom panic message provided by `msg`.",
+            ok = some_name,
+            err = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #[track_caller]
+            #func #expect(self, msg: &str) -> #some_ty {
+                // separate function to keep the panic machinery out of the hot path
+                #[cold]
+                #[inline(never)]
+                #[track_caller]
+                fn expect_failed(msg: &str) -> ! {
+                    ::core::panic!("{}", msg)
+                }
+
+                match self {
+                    #some_x => #x,
+                    _ => expect_failed(msg),
+                }
+            }
+        });
+    }
+
+    // unwrap
+    {
+        let unwrap = container.method("unwrap");
+        let msg = attrs.unwrap_msg.clone().unwrap_or_else(|| {
+            format!(
+                "called `{name}::{unwrap}()` on an `{err}` value",
+                name = name,
+                unwrap = unwrap,
+                err = none_name
+            )
+        });
+        let doc = format!(
+            "Returns the contained `{ok}` value, consuming `self`. Equivalent to `Result::unwrap`.
+
+# Panics
+
+Panics if the value is an `{err}`.",
+            ok = some_name,
+            err = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #[track_caller]
+            #func #unwrap(self) -> #some_ty {
+                // separate function to keep the panic machinery out of the hot path
+                #[cold]
+                #[inline(never)]
+                #[track_caller]
+                fn unwrap_failed() -> ! {
+                    ::core::panic!("{}", #msg)
+                }
+
+                match self {
+                    #some_x => #x,
+                    _ => unwrap_failed(),
+                }
+            }
+        });
+    }
+
+    // expect_err
+    {
+        let expect_err = container.method("expect_err");
+        let doc = format!(
+            "Returns the contained `{err}` error, consuming `self`. Equivalent to `Result::expect_err`.
+
+# Panics
+
+Panics if the value is an `{ok}` with a custom panic message provided by `msg`.",
+            ok = some_name, err = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #[track_caller]
+            #func #expect_err(self, msg: &str) -> #err_ty {
+                // separate function to keep the panic machinery out of the hot path
+                #[cold]
+                #[inline(never)]
+                #[track_caller]
+                fn expect_failed(msg: &str) -> ! {
+                    ::core::panic!("{}", msg)
+                }
+
+                match self {
+                    #err_e => e,
+                    _ => expect_failed(msg),
+                }
+            }
+        });
+    }
+
+    // unwrap_err
+    {
+        let unwrap_err = container.method("unwrap_err");
+        let msg = format!(
+            "called `{name}::{unwrap_err}()` on an `{ok}` value",
+            name = name,
+            unwrap_err = unwrap_err,
+            ok = some_name
+        );
+        let doc = format!(
+            "Returns the contained `{err}` error, consuming `self`. Equivalent to `Result::unwrap_err`.
+
+# Panics
+
+Panics if the value is an `{ok}`.",
+            ok = some_name, err = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #[track_caller]
+            #func #unwrap_err(self) -> #err_ty {
+                // separate function to keep the panic machinery out of the hot path
+                #[cold]
+                #[inline(never)]
+                #[track_caller]
+                fn unwrap_failed() -> ! {
+                    ::core::panic!("{}", #msg)
+                }
+
+                match self {
+                    #err_e => e,
+                    _ => unwrap_failed(),
+                }
+            }
+        });
+    }
+
+    // unwrap_or
+    {
+        let unwrap_or = container.method("unwrap_or");
+        let doc = format!(
+            "Returns the contained `{ok}` value or a provided default. Equivalent to `Result::unwrap_or`.",
+            ok = some_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #unwrap_or(self, default: #some_ty) -> #some_ty {
+                match self {
+                    #some_x => #x,
+                    _ => default,
+                }
+            }
+        });
+    }
+
+    // unwrap_or_else
+    {
+        let unwrap_or_else = container.method("unwrap_or_else");
+        let doc = format!(
+            "Returns the contained `{ok}` value or computes it from the error with a closure. Equivalent to `Result::unwrap_or_else`.",
+            ok = some_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #unwrap_or_else<#f>(self, f: #f) -> #some_ty
+            where
+                #f: FnOnce(#err_ty) -> #some_ty,
+            {
+                match self {
+                    #some_x => #x,
+                    #err_e => f(e),
+                }
+            }
+        });
+    }
+
+    // unwrap_or_default
+    {
+        let unwrap_or_default = container.method("unwrap_or_default");
+        let doc = format!(
+            "Returns the contained `{ok}` value or its default. Equivalent to `Result::unwrap_or_default`.",
+            ok = some_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #unwrap_or_default(self) -> #some_ty
+            where
+                #some_ty: ::core::default::Default,
+            {
+                match self {
+                    #some_x => #x,
+                    _ => ::core::default::Default::default(),
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,220 @@
+use super::*;
+
+pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStream) {
+    #[allow(unused_variables)]
+    #[rustfmt::skip]
+    let DataContainer {
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
+    } = *container;
+
+    let x = container.some_value("x");
+    let some_x = container.some_pat(quote! {x});
+    let some_ref = container.some_pat(quote! {ref x});
+    let err_e = container.err_pat(quote! {e});
+    let err_ref = container.err_pat(quote! {ref e});
+    let err_ty = container.err_ty();
+    let err_from_e = container.err_with(quote! {e});
+    // these keep the `Ok`-like variant
+    let fx_arms = container.some_arms(quote! {x}, |v| {
+        container.some_variant_with(v, quote! {f(#x)})
+    });
+    let x_arms = container.some_arms(quote! {x}, |v| container.some_variant_with(v, &x));
+
+    /////////////////////////////////////////////////////////////////////////
+    // Transforming contained values
+    /////////////////////////////////////////////////////////////////////////
+
+    let (u, d, e, f) = (
+        container.param("U"),
+        container.param("D"),
+        container.param("E"),
+        container.param("F"),
+    );
+
+    // ok
+    {
+        let ok = container.method("ok");
+        let doc = format!(
+            "Converts from `{name}` to `Option`, discarding the error, if any. Equivalent to `Result::ok`.",
+            name = name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #ok(self) -> #opt<#some_ty> {
+                match self {
+                    #some_x => #opt::Some(#x),
+                    _ => #opt::None,
+                }
+            }
+        });
+    }
+
+    // err
+    {
+        let err = container.method("err");
+        let doc = format!(
+            "Converts from `{name}` to `Option`, discarding the `{ok}` value, if any. Equivalent to `Result::err`.",
+            name = name,
+            ok = some_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #err(self) -> #opt<#err_ty> {
+                match self {
+                    #err_e => #opt::Some(e),
+                    _ => #opt::None,
+                }
+            }
+        });
+    }
+
+    // map
+    if let Some(generic) = container.ok_generic() {
+        let map = container.method("map");
+        let doc = format!(
+            "Maps a `{name}<{ty}>` to `{name}<U>` by applying a function to a contained `{ok}` value, leaving an `{err}` value untouched. Equivalent to `Result::map`.",
+            name = name, ty = generic, ok = some_name, err = none_name,
+        );
+        let u_bounds = container.bounds_for(&u);
+        let ret = container.name_with(&u);
+        let some_u = container.some_ty_with(&u);
+        let u_predicates = container.predicates_for(&u);
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #map<#u, #f>(self, f: #f) -> #ret
+            where
+                #f: FnOnce(#some_ty) -> #some_u,
+                #u_bounds
+                #u_predicates
+            {
+                match self {
+                    #fx_arms
+                    #err_e => #err_from_e,
+                }
+            }
+        });
+    }
+
+    // map_err
+    if let Some(err_generic) = container.err_generic() {
+        let map_err = container.method("map_err");
+        let doc = format!(
+            "Maps a `{name}<.., {ty}>` to `{name}<.., {f}>` by applying a function to a contained `{err}` error, leaving an `{ok}` value untouched. Equivalent to `Result::map_err`.",
+            name = name, ty = err_generic, f = e, ok = some_name, err = none_name,
+        );
+        let ret = container.name_replacing(&err_generic, &e);
+        let err_from_fe = container.err_with(quote! {f(e)});
+        let err_e2 = substitute(err_ty, &err_generic, &e);
+        let e_predicates = container.predicates_replacing(&err_generic, &e);
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #map_err<#e, #f>(self, f: #f) -> #ret
+            where
+                #f: FnOnce(#err_ty) -> #err_e2,
+                #e_predicates
+            {
+                match self {
+                    #x_arms
+                    #err_e => #err_from_fe,
+                }
+            }
+        });
+    }
+
+    // map_or
+    {
+        let map_or = container.method("map_or");
+        let doc = format!(
+            "Returns the provided default (if `{err}`), or applies a function to the contained value (if `{ok}`). Equivalent to `Result::map_or`.",
+            ok = some_name, err = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #map_or<#u, #f>(self, default: #u, f: #f) -> #u
+            where
+                #f: FnOnce(#some_ty) -> #u,
+            {
+                match self {
+                    #some_x => f(#x),
+                    _ => default,
+                }
+            }
+        });
+    }
+
+    // map_or_else
+    {
+        let map_or_else = container.method("map_or_else");
+        let doc = format!(
+            "Maps a `{name}` to `U` by applying `default` to a contained `{err}` error, or `f` to a contained `{ok}` value. Equivalent to `Result::map_or_else`.",
+            name = name, ok = some_name, err = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #map_or_else<#u, #d, #f>(self, default: #d, f: #f) -> #u
+            where
+                #d: FnOnce(#err_ty) -> #u,
+                #f: FnOnce(#some_ty) -> #u,
+            {
+                match self {
+                    #some_x => f(#x),
+                    #err_e => default(e),
+                }
+            }
+        });
+    }
+
+    // inspect
+    if !container.is_composite() {
+        let inspect = container.method("inspect");
+        let doc = format!(
+            "Calls a function with a reference to the contained value if `{ok}`. Equivalent to `Result::inspect`.",
+            ok = some_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #inspect<#f>(self, f: #f) -> Self
+            where
+                #f: FnOnce(&#some_ty),
+            {
+                if let #some_ref = self {
+                    f(#x);
+                }
+                self
+            }
+        });
+    }
+
+    // inspect_err
+    {
+        let inspect_err = container.method("inspect_err");
+        let doc = format!(
+            "Calls a function with a reference to the contained error if `{err}`. Equivalent to `Result::inspect_err`.",
+            err = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #inspect_err<#f>(self, f: #f) -> Self
+            where
+                #f: FnOnce(&#err_ty),
+            {
+                if let #err_ref = self {
+                    f(e);
+                }
+                self
+            }
+        });
+    }
+}
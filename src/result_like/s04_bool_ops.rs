@@ -0,0 +1,184 @@
+use super::*;
+
+pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStream) {
+    #[allow(unused_variables)]
+    #[rustfmt::skip]
+    let DataContainer {
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
+    } = *container;
+
+    let x = container.some_value("x");
+    let some_any = container.some_pat(quote! {_});
+    let some_x = container.some_pat(quote! {x});
+    let err_e = container.err_pat(quote! {e});
+    let err_ty = container.err_ty();
+    let err_from_e = container.err_with(quote! {e});
+    // keeps the `Ok`-like variant
+    let x_arms = container.some_arms(quote! {x}, |v| container.some_variant_with(v, &x));
+
+    /////////////////////////////////////////////////////////////////////////
+    // Boolean operations on the values, eager and lazy
+    /////////////////////////////////////////////////////////////////////////
+
+    let (u, e, f) = (
+        container.param("U"),
+        container.param("E"),
+        container.param("F"),
+    );
+
+    // and
+    {
+        let and = container.method("and");
+        let doc = format!(
+            "Returns `res` if the `{name}` is an `{ok}`, otherwise returns the `{err}` of `self`. Equivalent to `Result::and`.",
+            name = name, ok = some_name, err = none_name,
+        );
+        if container.ok_generic().is_some() {
+            let u_bounds = container.bounds_for(&u);
+            let u_predicates = container.predicates_for(&u);
+            let name_u = container.name_with(&u);
+            impl_block.extend(quote! {
+                #[doc = #doc]
+                #func #and<#u>(self, res: #name_u) -> #name_u
+                where
+                    #u_bounds
+                    #u_predicates
+                {
+                    match self {
+                        #some_any => res,
+                        #err_e => #err_from_e,
+                    }
+                }
+            });
+        } else {
+            impl_block.extend(quote! {
+                #[doc = #doc]
+                #func #and(self, res: Self) -> Self {
+                    match self {
+                        #some_any => res,
+                        _ => self,
+                    }
+                }
+            });
+        }
+    }
+
+    // and_then
+    {
+        let and_then = container.method("and_then");
+        let doc = format!(
+            "Calls `f` if the `{name}` is an `{ok}`, otherwise returns the `{err}` of `self`. Equivalent to `Result::and_then`.",
+            name = name, ok = some_name, err = none_name,
+        );
+        if container.ok_generic().is_some() {
+            let u_bounds = container.bounds_for(&u);
+            let u_predicates = container.predicates_for(&u);
+            let name_u = container.name_with(&u);
+            impl_block.extend(quote! {
+                #[doc = #doc]
+                #func #and_then<#u, #f>(self, f: #f) -> #name_u
+                where
+                    #f: FnOnce(#some_ty) -> #name_u,
+                    #u_bounds
+                    #u_predicates
+                {
+                    match self {
+                        #some_x => f(#x),
+                        #err_e => #err_from_e,
+                    }
+                }
+            });
+        } else {
+            impl_block.extend(quote! {
+                #[doc = #doc]
+                #func #and_then<#f>(self, f: #f) -> Self
+                where
+                    #f: FnOnce(#some_ty) -> Self,
+                {
+                    match self {
+                        #some_x => f(#x),
+                        _ => self,
+                    }
+                }
+            });
+        }
+    }
+
+    // or
+    {
+        let or = container.method("or");
+        let doc = format!(
+            "Returns `res` if the `{name}` is an `{err}`, otherwise returns the `{ok}` value of `self`. Equivalent to `Result::or`.",
+            name = name, ok = some_name, err = none_name,
+        );
+        if let Some(err_generic) = container.err_generic() {
+            let e_predicates = container.predicates_replacing(&err_generic, &e);
+            let name_e = container.name_replacing(&err_generic, &e);
+            impl_block.extend(quote! {
+                #[doc = #doc]
+                #func #or<#e>(self, res: #name_e) -> #name_e
+                where
+                    #e_predicates
+                {
+                    match self {
+                        #x_arms
+                        _ => res,
+                    }
+                }
+            });
+        } else {
+            impl_block.extend(quote! {
+                #[doc = #doc]
+                #func #or(self, res: Self) -> Self {
+                    match self {
+                        #some_any => self,
+                        _ => res,
+                    }
+                }
+            });
+        }
+    }
+
+    // or_else
+    {
+        let or_else = container.method("or_else");
+        let doc = format!(
+            "Calls `f` with the error if the `{name}` is an `{err}`, otherwise returns the `{ok}` value of `self`. Equivalent to `Result::or_else`.",
+            name = name, ok = some_name, err = none_name,
+        );
+        if let Some(err_generic) = container.err_generic() {
+            let e_predicates = container.predicates_replacing(&err_generic, &e);
+            let name_e = container.name_replacing(&err_generic, &e);
+            impl_block.extend(quote! {
+                #[doc = #doc]
+                #func #or_else<#e, #f>(self, f: #f) -> #name_e
+                where
+                    #f: FnOnce(#err_ty) -> #name_e,
+                    #e_predicates
+                {
+                    match self {
+                        #x_arms
+                        #err_e => f(e),
+                    }
+                }
+            });
+        } else {
+            impl_block.extend(quote! {
+                #[doc = #doc]
+                #func #or_else<#f>(self, f: #f) -> Self
+                where
+                    #f: FnOnce(#err_ty) -> Self,
+                {
+                    match self {
+                        #some_any => self,
+                        #err_e => f(e),
+                    }
+                }
+            });
+        }
+    }
+}
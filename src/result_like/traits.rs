@@ -0,0 +1,51 @@
+use super::*;
+
+pub(crate) fn add_external(container: &DataContainer, additional_impls: &mut TokenStream) {
+    #[allow(unused_variables)]
+    #[rustfmt::skip]
+    let DataContainer {
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
+    } = *container;
+
+    let x = container.some_value("x");
+    let some_x = container.some_pat(quote! {x});
+    let some_from_x = container.some_with(quote! {x});
+    let err_e = container.err_pat(quote! {e});
+    let err_ty = container.err_ty();
+    let err_from_e = container.err_with(quote! {e});
+    let res = quote! {::core::result::Result};
+
+    // Self: From<Result>
+    if attrs.implements(TraitImpl::From) {
+        additional_impls.extend(quote! {
+            #[automatically_derived]
+            #imp ::core::convert::From<#res<#some_ty, #err_ty>> for #full_name #imp_where {
+                fn from(src: #res<#some_ty, #err_ty>) -> Self {
+                    match src {
+                        #res::Ok(x) => #some_from_x,
+                        #res::Err(e) => #err_from_e,
+                    }
+                }
+            }
+        });
+    }
+
+    // Result: From<Self>
+    if attrs.implements(TraitImpl::From) {
+        additional_impls.extend(quote! {
+            #[automatically_derived]
+            #imp ::core::convert::From<#full_name> for #res<#some_ty, #err_ty> #imp_where {
+                fn from(src: #full_name) -> Self {
+                    match src {
+                        #some_x => #res::Ok(#x),
+                        #err_e => #res::Err(e),
+                    }
+                }
+            }
+        });
+    }
+}
@@ -1,23 +1,28 @@
 use super::*;
+use convert_case::{Case, Casing};
 use syn::Ident;
 
 pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStream) {
     #[allow(unused_variables)]
     #[rustfmt::skip]
     let DataContainer {
-        ref name, ref full_name, ref full_name_string, ref some, ref none, ref some_name, ref none_name,
-        ref some_name_snake, ref none_name_snake, ref some_ty, ref some_ty_name, is_generic, ref bounds, ref imp,
-        ref func, ref c_func, ref opt
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
     } = *container;
 
+    let some_any = container.some_pat(quote! {_});
+
     /////////////////////////////////////////////////////////////////////////
     // Querying the contained values
     /////////////////////////////////////////////////////////////////////////
 
     // is_some
-    {
+    if let [_] = some_variants.as_slice() {
         let is_some = Ident::new(
-            &format!("is_{}", some_name_snake),
+            &attrs.method_name("is_some", &format!("is_{}", some_name_snake)),
             some.clone().into_iter().last().unwrap().span(),
         );
         let doc = format!(
@@ -28,19 +33,25 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
         // some_match might have {} or (), so match against none instead
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func #is_some(&self) -> bool {
-                matches!(*self, #some(_))
+            #c_func #is_some(&self) -> bool {
+                ::core::matches!(*self, #some_any)
             }
         });
+    } else {
+        // one method per `Some`-like variant, `!is_none()` checks for any of them
+        for variant in some_variants {
+            let is_variant = variant_check(container, &variant.ident);
+            impl_block.extend(is_variant);
+        }
     }
 
     // is_some_and
     // TODO:
 
     // is_none
-    {
+    if let [_] = none_variants.as_slice() {
         let is_none = Ident::new(
-            &format!("is_{}", none_name_snake),
+            &attrs.method_name("is_none", &format!("is_{}", none_name_snake)),
             none.clone().into_iter().last().unwrap().span(),
         );
         let doc = format!(
@@ -50,12 +61,41 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func #is_none(&self) -> bool {
-                matches!(*self, #none)
+            #c_func #is_none(&self) -> bool {
+                ::core::matches!(*self, #none_pat)
             }
         });
+    } else {
+        // one method per `None`-like variant, `!is_some()` checks for any of them
+        for variant in none_variants {
+            let is_variant = variant_check(container, &variant.ident);
+            impl_block.extend(is_variant);
+        }
     }
 
     // is_none_or
     // unstable
 }
+
+/// `is_<variant>` for one of several `Some`-like or `None`-like variants
+fn variant_check(container: &DataContainer, ident: &Ident) -> TokenStream {
+    let DataContainer {
+        ref name,
+        ref c_func,
+        ref attrs,
+        ..
+    } = *container;
+    let key = format!("is_{}", ident.to_string().to_case(Case::Snake));
+    let is_variant = Ident::new(&attrs.method_name(&key, &key), ident.span());
+    let doc = format!(
+        "Returns `true` if the `{name}` is a `{variant}` value.",
+        name = name,
+        variant = ident,
+    );
+    quote! {
+        #[doc = #doc]
+        #c_func #is_variant(&self) -> bool {
+            ::core::matches!(*self, #name::#ident { .. })
+        }
+    }
+}
@@ -4,30 +4,53 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
     #[allow(unused_variables)]
     #[rustfmt::skip]
     let DataContainer {
-        ref name, ref full_name, ref full_name_string, ref some, ref none, ref some_name, ref none_name,
-        ref some_name_snake, ref none_name_snake, ref some_ty, ref some_ty_name, is_generic, ref bounds, ref imp,
-        ref func, ref c_func, ref opt
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
     } = *container;
 
+    let none_arms = container.none_ref_arms();
+    let none_bounds = container.none_clone_bounds();
+    let x = container.some_value("x");
+    let some_ref = container.some_pat(quote! {ref x});
+    let some_mut = container.some_pat(quote! {ref mut x});
+    let pinned = quote! {::core::pin::Pin::new_unchecked(#x)};
+    // these keep the `Some`-like variant
+    let ref_arms = container.some_arms(quote! {ref x}, |v| container.some_variant_with(v, &x));
+    let mut_arms = container.some_arms(quote! {ref mut x}, |v| container.some_variant_with(v, &x));
+    let ref_pinned_arms = container.some_arms(quote! {ref x}, |v| {
+        let some_pinned = container.some_variant_with(v, &pinned);
+        quote! { unsafe { #some_pinned } }
+    });
+    let mut_pinned_arms = container.some_arms(quote! {ref mut x}, |v| {
+        container.some_variant_with(v, &pinned)
+    });
+
     /////////////////////////////////////////////////////////////////////////
     // Adapter for working with references
     /////////////////////////////////////////////////////////////////////////
 
+    let a = container.lifetime("a");
+
     // as_ref
     if is_generic {
+        let as_ref = container.method("as_ref");
         let doc = format!(
             "Converts from `&{name}<{ty}>` to `{name}<&{ty}>`. Equivalent to `Option::as_ref`.",
             name = name,
             ty = some_ty_name,
         );
-        let where_clause = container.where_clause_for(quote! {&'a #some_ty});
-        // can't be c_func right now because of trait bounds in const functions aren't available in our MSRV
+        let ref_bounds = container.bounds_for(quote! {&#a #some_ty});
+        let ret = container.name_with(quote! {&#a #some_ty});
+        // only const with #[optional(const)], because trait bounds in const functions aren't available in our MSRV
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func as_ref<'a>(&'a self) -> #name<&'a #some_ty> #where_clause {
+            #c_func #as_ref<#a>(&#a self) -> #ret where #ref_bounds #none_bounds {
                 match *self {
-                    #some(ref x) => #some(x),
-                    _ => #none,
+                    #ref_arms
+                    #none_arms
                 }
             }
         });
@@ -35,19 +58,22 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // as_mut
     if is_generic {
+        let as_mut = container.method("as_mut");
         let doc = format!(
             "Converts from `&mut {name}<{ty}>` to `{name}<&mut {ty}>`. Equivalent to `Option::as_mut`.",
             name = name,
             ty = some_ty_name,
         );
-        let where_clause = container.where_clause_for(quote! {&'a mut #some_ty});
-        // can't be c_func right now because of &mut: see https://github.com/rust-lang/rust/issues/57349
+        let mut_bounds = container.bounds_for(quote! {&#a mut #some_ty});
+        let ret = container.name_with(quote! {&#a mut #some_ty});
+        // only const with #[optional(const)], because &mut in const functions isn't available in our MSRV
+        // (https://github.com/rust-lang/rust/issues/57349)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func as_mut<'a>(&'a mut self) -> #name<&'a mut #some_ty> #where_clause {
+            #c_func #as_mut<#a>(&#a mut self) -> #ret where #mut_bounds #none_bounds {
                 match *self {
-                    #some(ref mut x) => #some(x),
-                    _ => #none,
+                    #mut_arms
+                    #none_arms
                 }
             }
         });
@@ -55,22 +81,24 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // as_pin_ref
     if is_generic {
+        let as_pin_ref = container.method("as_pin_ref");
         let doc = format!(
             "Converts from `Pin<&{name}<{ty}>>` to `{name}<Pin<&{ty}>>`. Equivalent to `Option::as_pin_ref`.",
             name = name,
             ty = some_ty_name,
         );
-        let ret_inner = quote! {::std::pin::Pin<&'a #some_ty>};
-        let where_clause = container.where_clause_for(&ret_inner);
+        let ret_inner = quote! {::core::pin::Pin<&#a #some_ty>};
+        let pinned_bounds = container.bounds_for(&ret_inner);
+        let ret = container.name_with(&ret_inner);
         // can't be c_func right now because of Pin::<&'a T>::get_ref (https://github.com/rust-lang/rust/issues/76654)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func as_pin_ref<'a>(self: ::std::pin::Pin<&'a Self>) -> #name<#ret_inner> #where_clause {
-                match ::std::pin::Pin::get_ref(self) {
+            #func #as_pin_ref<#a>(self: ::core::pin::Pin<&#a Self>) -> #ret where #pinned_bounds #none_bounds {
+                match ::core::pin::Pin::get_ref(self) {
                     // SAFETY: `x` is guaranteed to be pinned because it comes from `self`
                     // which is pinned.
-                    #some(ref x) => unsafe { #some(::std::pin::Pin::new_unchecked(x)) },
-                    _ => #none,
+                    #ref_pinned_arms
+                    #none_arms
                 }
             }
         });
@@ -78,24 +106,26 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // as_pin_mut
     if is_generic {
+        let as_pin_mut = container.method("as_pin_mut");
         let doc = format!(
             "Converts from `Pin<&mut {name}<{ty}>>` to `{name}<Pin<&mut {ty}>>`. Equivalent to `Option::as_pin_mut`.",
             name = name,
             ty = some_ty_name,
         );
-        let ret_inner = quote! {::std::pin::Pin<&'a mut #some_ty>};
-        let where_clause = container.where_clause_for(&ret_inner);
+        let ret_inner = quote! {::core::pin::Pin<&#a mut #some_ty>};
+        let pinned_bounds = container.bounds_for(&ret_inner);
+        let ret = container.name_with(&ret_inner);
         // can't be c_func right now because of Pin::<&'a mut T>::get_unchecked_mut (https://github.com/rust-lang/rust/issues/76654)
         // and &mut (https://github.com/rust-lang/rust/issues/57349)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func as_pin_mut<'a>(self: ::std::pin::Pin<&'a mut Self>) -> #name<#ret_inner> #where_clause {
+            #func #as_pin_mut<#a>(self: ::core::pin::Pin<&#a mut Self>) -> #ret where #pinned_bounds #none_bounds {
                 // SAFETY: `get_unchecked_mut` is never used to move the `Option` inside `self`.
                 // `x` is guaranteed to be pinned because it comes from `self` which is pinned.
                 unsafe {
-                    match ::std::pin::Pin::get_unchecked_mut(self) {
-                        #some(ref mut x) => #some(::std::pin::Pin::new_unchecked(x)),
-                        _ => #none,
+                    match ::core::pin::Pin::get_unchecked_mut(self) {
+                        #mut_pinned_arms
+                        #none_arms
                     }
                 }
             }
@@ -103,16 +133,17 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
     }
 
     // as_slice
-    {
+    if !container.is_composite() {
+        let as_slice = container.method("as_slice");
         let doc = format!(
             "Returns a slice of the contained value, if any. Equivalent to `Option::as_slice`."
         );
-        // can't be c_func right now because from_ref is not yet const in our MSRV
+        // only const with #[optional(const)], because from_ref is not const in our MSRV
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func as_slice(&self) -> &[#some_ty] {
+            #c_func #as_slice(&self) -> &[#some_ty] {
                 match *self {
-                    #some(ref x) => ::std::slice::from_ref(x),
+                    #some_ref => ::core::slice::from_ref(#x),
                     _ => &[],
                 }
             }
@@ -120,16 +151,17 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
     }
 
     // as_mut_slice
-    {
+    if !container.is_composite() {
+        let as_mut_slice = container.method("as_mut_slice");
         let doc = format!(
             "Returns a mutable slice of the contained value, if any. Equivalent to `Option::as_mut_slice`."
         );
-        // can't be c_func right now because from_mut is not const yet
+        // only const with #[optional(const)], because from_mut is not const in our MSRV
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func as_mut_slice(&mut self) -> &mut [#some_ty] {
+            #c_func #as_mut_slice(&mut self) -> &mut [#some_ty] {
                 match *self {
-                    #some(ref mut x) => ::std::slice::from_mut(x),
+                    #some_mut => ::core::slice::from_mut(#x),
                     _ => &mut [],
                 }
             }
@@ -4,17 +4,31 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
     #[allow(unused_variables)]
     #[rustfmt::skip]
     let DataContainer {
-        ref name, ref full_name, ref full_name_string, ref some, ref none, ref some_name, ref none_name,
-        ref some_name_snake, ref none_name_snake, ref some_ty, ref some_ty_name, is_generic, ref bounds, ref imp,
-        ref func, ref c_func, ref opt
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
     } = *container;
 
+    let x = container.some_value("x");
+    let some_x = container.some_pat(quote! {x});
+
     /////////////////////////////////////////////////////////////////////////
     // Getting to contained values
     /////////////////////////////////////////////////////////////////////////
 
+    let f = container.param("F");
+
+    let constness = if container.is_const() {
+        quote! {const}
+    } else {
+        quote! {}
+    };
+
     // expect
     {
+        let expect = container.method("expect");
         let doc = format!(
             "Returns the contained `{some}` value, consuming `self`. Equivalent to `Option::expect`.
 
@@ -23,13 +37,24 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 Panics if the value is a `{none}` with a custom panic message provided by `msg`.",
             some = some_name, none = none_name,
         );
-        // can't be c_func right now because of `panic`'s formatting. std uses nightly-only functions from feature(core_panic)
+        // only const with #[optional(const)], because of `panic`'s formatting and destructors in our MSRV
+        let (setup, scrutinee, some_x) = move_out(container);
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func expect(self, msg: &str) -> #some_ty {
-                match self {
-                    #some(x) => x,
-                    _ => panic!("{}", msg),
+            #[track_caller]
+            #c_func #expect(self, msg: &str) -> #some_ty {
+                // separate function to keep the panic machinery out of the hot path
+                #[cold]
+                #[inline(never)]
+                #[track_caller]
+                #constness fn expect_failed(msg: &str) -> ! {
+                    ::core::panic!("{}", msg)
+                }
+
+                #setup
+                match #scrutinee {
+                    #some_x,
+                    _ => expect_failed(msg),
                 }
             }
         });
@@ -37,11 +62,24 @@ Panics if the value is a `{none}` with a custom panic message provided by `msg`.
 
     // unwrap
     {
-        let msg = format!(
-            "called `{name}::unwrap()` on a `{none}` value",
-            name = name,
-            none = none_name
-        );
+        let unwrap = container.method("unwrap");
+        // the default message names the `None`-like variant that was found
+        let failed_arms = match attrs.unwrap_msg {
+            Some(ref msg) => quote! { _ => unwrap_failed(#msg), },
+            None => {
+                let arms = none_variants.iter().map(|variant| {
+                    let ident = &variant.ident;
+                    let msg = format!(
+                        "called `{name}::{unwrap}()` on a `{none}` value",
+                        name = name,
+                        unwrap = unwrap,
+                        none = ident
+                    );
+                    quote! { #name::#ident { .. } => unwrap_failed(#msg), }
+                });
+                quote! { #(#arms)* }
+            }
+        };
         let doc = format!(
             "Returns the contained `{some}` value, consuming `self`. Equivalent to `Option::unwrap`.
 
@@ -50,13 +88,24 @@ Panics if the value is a `{none}` with a custom panic message provided by `msg`.
 Panics if the value is a `{none}`.",
             some = some_name, none = none_name,
         );
-        // can't be c_func right now because of `panic`'s formatting. std uses nightly-only functions from feature(core_panic)
+        // only const with #[optional(const)], because of `panic`'s formatting and destructors in our MSRV
+        let (setup, scrutinee, some_x) = move_out(container);
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func unwrap(self) -> #some_ty {
-                match self {
-                    #some(x) => x,
-                    _ => panic!("{}", #msg),
+            #[track_caller]
+            #c_func #unwrap(self) -> #some_ty {
+                // separate function to keep the panic machinery out of the hot path
+                #[cold]
+                #[inline(never)]
+                #[track_caller]
+                #constness fn unwrap_failed(msg: &str) -> ! {
+                    ::core::panic!("{}", msg)
+                }
+
+                #setup
+                match #scrutinee {
+                    #some_x,
+                    #failed_arms
                 }
             }
         });
@@ -64,6 +113,7 @@ Panics if the value is a `{none}`.",
 
     // unwrap_or
     {
+        let unwrap_or = container.method("unwrap_or");
         let doc = format!(
             "Returns the contained `{some}` value or a provided default. Equivalent to `Option::unwrap_or`.",
             some = some_name,
@@ -71,9 +121,9 @@ Panics if the value is a `{none}`.",
         // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func unwrap_or(self, default: #some_ty) -> #some_ty {
+            #func #unwrap_or(self, default: #some_ty) -> #some_ty {
                 match self {
-                    #some(x) => x,
+                    #some_x => #x,
                     _ => default,
                 }
             }
@@ -82,6 +132,7 @@ Panics if the value is a `{none}`.",
 
     // unwrap_or_else
     {
+        let unwrap_or_else = container.method("unwrap_or_else");
         let doc = format!(
             "Returns the contained `{some}` value or computes it from a closure. Equivalent to `Option::unwrap_or_else`.",
             some = some_name,
@@ -89,12 +140,12 @@ Panics if the value is a `{none}`.",
         // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func unwrap_or_else<F>(self, f: F) -> #some_ty
+            #func #unwrap_or_else<#f>(self, f: #f) -> #some_ty
             where
-                F: FnOnce() -> #some_ty,
+                #f: FnOnce() -> #some_ty,
             {
                 match self {
-                    #some(x) => x,
+                    #some_x => #x,
                     _ => f(),
                 }
             }
@@ -103,6 +154,7 @@ Panics if the value is a `{none}`.",
 
     // unwrap_or_default
     {
+        let unwrap_or_default = container.method("unwrap_or_default");
         let doc = format!(
             "Returns the contained `{some}` value or its default. Equivalent to `Option::unwrap_or_default`.",
             some = some_name,
@@ -110,13 +162,13 @@ Panics if the value is a `{none}`.",
         // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func unwrap_or_default(self) -> #some_ty
+            #func #unwrap_or_default(self) -> #some_ty
             where
-                #some_ty: ::std::default::Default,
+                #some_ty: ::core::default::Default,
             {
                 match self {
-                    #some(x) => x,
-                    _ => ::std::default::Default::default(),
+                    #some_x => #x,
+                    _ => ::core::default::Default::default(),
                 }
             }
         });
@@ -124,6 +176,7 @@ Panics if the value is a `{none}`.",
 
     // unwrap_unchecked
     {
+        let unwrap_unchecked = container.method("unwrap_unchecked");
         let doc = format!(
             "Returns the contained `{some}` value without checking, consuming `self`. Equivalent to `Option::unwrap_unchecked`.
             
@@ -132,16 +185,52 @@ Panics if the value is a `{none}`.",
 The caller must guarantee that the value is a `{some}`. Otherwise, undefined behavior occurs.",
             some = some_name,
         );
-        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        // only const with #[optional(const)], because of destructors in our MSRV
+        let (setup, scrutinee, some_x) = move_out(container);
         impl_block.extend(quote! {
             #[doc = #doc]
-            pub unsafe fn unwrap_unchecked(self) -> #some_ty {
-                match self {
-                    #some(x) => x,
+            #[inline]
+            #vis #constness unsafe fn #unwrap_unchecked(self) -> #some_ty {
+                #setup
+                match #scrutinee {
+                    #some_x,
                     // SAFETY: the safety contract must be upheld by the caller.
-                    _ => unsafe { ::std::hint::unreachable_unchecked() },
+                    _ => unsafe { ::core::hint::unreachable_unchecked() },
                 }
             }
         });
     }
 }
+
+/// Returns the statements, match scrutinee and match arm to move the contained value out of `self`.
+///
+/// A `const fn` can't move out of `self` in a `match`, because the destructor of `Self` can't be
+/// evaluated at compile time, even if it is never called. With `#[optional(const)]`, `self` is
+/// instead wrapped in a `ManuallyDrop` and the value is read out of it. This is only done if the
+/// methods are actually const, since skipped fields and data in `None`-like variants would never
+/// be dropped otherwise.
+fn move_out(container: &DataContainer) -> (TokenStream, TokenStream, TokenStream) {
+    if container.is_const() {
+        let setup = quote! {
+            let this = ::core::mem::ManuallyDrop::new(self);
+            // SAFETY: `ManuallyDrop<Self>` has the same layout as `Self`
+            let this = unsafe { &*(&this as *const ::core::mem::ManuallyDrop<Self> as *const Self) };
+        };
+        let some_ref = container.some_pat(quote! {ref x});
+        let names = container.some_names("x");
+        let value = if container.is_composite() {
+            quote! { (#(::core::ptr::read(#names)),*) }
+        } else {
+            quote! { ::core::ptr::read(#(#names)*) }
+        };
+        let some_x = quote! {
+            // SAFETY: `this` is never dropped, so the value is only moved out once
+            #some_ref => unsafe { #value }
+        };
+        (setup, quote! {*this}, some_x)
+    } else {
+        let some_x = container.some_pat(quote! {x});
+        let x = container.some_value("x");
+        (quote! {}, quote! {self}, quote! {#some_x => #x})
+    }
+}
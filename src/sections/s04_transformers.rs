@@ -4,52 +4,87 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
     #[allow(unused_variables)]
     #[rustfmt::skip]
     let DataContainer {
-        ref name, ref full_name, ref full_name_string, ref some, ref none, ref some_name, ref none_name,
-        ref some_name_snake, ref none_name_snake, ref some_ty, ref some_ty_name, is_generic, ref bounds, ref imp,
-        ref func, ref c_func, ref opt
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
     } = *container;
 
+    let none_arms = container.none_arms();
+    let none_ref_arms = container.none_ref_arms();
+    let none_clone_bounds = container.none_clone_bounds();
+    let x = container.some_value("x");
+    let some_x = container.some_pat(quote! {x});
+    let some_ref = container.some_pat(quote! {ref x});
+    // these keep the `Some`-like variant
+    let fx_arms = container.some_arms(quote! {x}, |v| {
+        container.some_variant_with(v, quote! {f(#x)})
+    });
+    let deref_arms = container.some_arms(quote! {ref x}, |v| {
+        container.some_variant_with(v, quote! {::core::ops::Deref::deref(#x)})
+    });
+    let deref_mut_arms = container.some_arms(quote! {ref mut x}, |v| {
+        container.some_variant_with(v, quote! {::core::ops::DerefMut::deref_mut(#x)})
+    });
+
     /////////////////////////////////////////////////////////////////////////
     // Transforming contained values
     /////////////////////////////////////////////////////////////////////////
 
+    let (u, d, e, f) = (
+        container.param("U"),
+        container.param("D"),
+        container.param("E"),
+        container.param("F"),
+    );
+    let a = container.lifetime("a");
+
     // map
-    if is_generic {
+    if let Some(generic) = generic {
+        let map = container.method("map");
         let doc = format!(
             "Maps an `{name}<{ty}>` to `{name}<U>` by applying a function to a contained value. Equivalent to `Option::map`.",
-            name = name, ty = some_ty_name,
+            name = name, ty = generic,
         );
-        let u_bounds = container.bounds_for(quote! {U});
+        let u_bounds = container.bounds_for(&u);
+        let ret = container.name_with(&u);
+        let some_u = container.some_ty_with(&u);
+        let u_predicates = container.predicates_for(&u);
+        let none_defaults = container.none_defaults_for(&u);
         // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func map<U, F>(self, f: F) -> #name<U>
+            #func #map<#u, #f>(self, f: #f) -> #ret
             where
-                F: FnOnce(#some_ty) -> U,
+                #f: FnOnce(#some_ty) -> #some_u,
                 #u_bounds
+                #u_predicates
+                #none_defaults
             {
                 match self {
-                    #some(x) => #some(f(x)),
-                    _ => #none,
+                    #fx_arms
+                    #none_arms
                 }
             }
         });
     }
 
     // inspect
-    {
+    if !container.is_composite() {
+        let inspect = container.method("inspect");
         let doc = format!(
             "Applies a function to the contained value (if any). Equivalent to `Option::inspect`.",
         );
         // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func inspect<F>(self, f: F) -> Self
+            #func #inspect<#f>(self, f: #f) -> Self
             where
-                F: FnOnce(&#some_ty),
+                #f: FnOnce(&#some_ty),
             {
-                if let #some(ref x) = self {
-                    f(x);
+                if let #some_ref = self {
+                    f(#x);
                 }
                 self
             }
@@ -58,18 +93,19 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // map_or
     {
+        let map_or = container.method("map_or");
         let doc = format!(
             "Applies a function to the contained value (if any), or returns the provided default (if not). Equivalent to `Option::map_or`.",
         );
         // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func map_or<U, F>(self, default: U, f: F) -> U
+            #func #map_or<#u, #f>(self, default: #u, f: #f) -> #u
             where
-                F: FnOnce(#some_ty) -> U,
+                #f: FnOnce(#some_ty) -> #u,
             {
                 match self {
-                    #some(x) => f(x),
+                    #some_x => f(#x),
                     _ => default,
                 }
             }
@@ -78,19 +114,20 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // map_or_else
     {
+        let map_or_else = container.method("map_or_else");
         let doc = format!(
             "Applies a function to the contained value (if any), or calls the provided function to compute a default (if not). Equivalent to `Option::map_or_else`.",
         );
         // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func map_or_else<U, D, F>(self, default: D, f: F) -> U
+            #func #map_or_else<#u, #d, #f>(self, default: #d, f: #f) -> #u
             where
-                D: FnOnce() -> U,
-                F: FnOnce(#some_ty) -> U,
+                #d: FnOnce() -> #u,
+                #f: FnOnce(#some_ty) -> #u,
             {
                 match self {
-                    #some(x) => f(x),
+                    #some_x => f(#x),
                     _ => default(),
                 }
             }
@@ -99,6 +136,7 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // ok_or
     {
+        let ok_or = container.method("ok_or");
         let doc = format!(
             "Transforms the `{name}` into a `Result<{ty}, E>`, mapping `{some}(x)` to `Ok(x)` and `{none}` to `Err(err)`. Equivalent to `Option::ok_or`.",
             name = full_name_string, ty = some_ty_name, some = some_name, none = none_name,
@@ -106,10 +144,10 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
         // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func ok_or<E>(self, err: E) -> ::std::result::Result<#some_ty, E> {
+            #func #ok_or<#e>(self, err: #e) -> ::core::result::Result<#some_ty, #e> {
                 match self {
-                    #some(x) => ::std::result::Result::Ok(x),
-                    _ => ::std::result::Result::Err(err),
+                    #some_x => ::core::result::Result::Ok(#x),
+                    _ => ::core::result::Result::Err(err),
                 }
             }
         });
@@ -117,6 +155,7 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // ok_or_else
     {
+        let ok_or_else = container.method("ok_or_else");
         let doc = format!(
             "Transforms the `{name}` into a `Result<{ty}, E>`, mapping `{some}(x)` to `Ok(x)` and `{none}` to `Err(err())`. Equivalent to `Option::ok_or_else`.",
             name = full_name_string, ty = some_ty_name, some = some_name, none = none_name,
@@ -124,13 +163,13 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
         // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func ok_or_else<E, F>(self, err: F) -> ::std::result::Result<#some_ty, E>
+            #func #ok_or_else<#e, #f>(self, err: #f) -> ::core::result::Result<#some_ty, #e>
             where
-                F: FnOnce() -> E,
+                #f: FnOnce() -> #e,
             {
                 match self {
-                    #some(x) => ::std::result::Result::Ok(x),
-                    _ => ::std::result::Result::Err(err()),
+                    #some_x => ::core::result::Result::Ok(#x),
+                    _ => ::core::result::Result::Err(err()),
                 }
             }
         });
@@ -138,23 +177,26 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // as_deref
     if is_generic {
+        let as_deref = container.method("as_deref");
         let doc = format!(
             "Creates a `{name}<&{ty}::Target>` from an `&{name}<{ty}>`. Equivalent to `Option::as_deref`.",
             name = name, ty = some_ty_name,
         );
-        let ret_inner = quote! {&'a <#some_ty as ::std::ops::Deref>::Target};
+        let ret_inner = quote! {&#a <#some_ty as ::core::ops::Deref>::Target};
         let target_bounds = container.bounds_for(&ret_inner);
+        let ret = container.name_with(&ret_inner);
         // can't be c_func right now because of trait bounds (https://github.com/rust-lang/rust/issues/67792)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func as_deref<'a>(&'a self) -> #name<#ret_inner>
+            #func #as_deref<#a>(&#a self) -> #ret
             where
-                #some_ty: ::std::ops::Deref,
+                #some_ty: ::core::ops::Deref,
                 #target_bounds
+                #none_clone_bounds
             {
                 match self {
-                    #some(ref x) => #some(x.deref()),
-                    _ => #none,
+                    #deref_arms
+                    #none_ref_arms
                 }
             }
         });
@@ -162,24 +204,27 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // as_deref_mut
     if is_generic {
+        let as_deref_mut = container.method("as_deref_mut");
         let doc = format!(
             "Creates a `{name}<&mut {ty}::Target>` from an `&mut {name}<{ty}>`. Equivalent to `Option::as_deref_mut`.",
             name = name, ty = some_ty_name,
         );
-        let ret_inner = quote! {&'a mut <#some_ty as ::std::ops::Deref>::Target};
+        let ret_inner = quote! {&#a mut <#some_ty as ::core::ops::Deref>::Target};
         let target_bounds = container.bounds_for(&ret_inner);
+        let ret = container.name_with(&ret_inner);
         // can't be c_func right now because of trait bounds (https://github.com/rust-lang/rust/issues/67792)
         // and &mut (https://github.com/rust-lang/rust/issues/57349)
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func as_deref_mut<'a>(&'a mut self) -> #name<#ret_inner>
+            #func #as_deref_mut<#a>(&#a mut self) -> #ret
             where
-                #some_ty: ::std::ops::DerefMut,
+                #some_ty: ::core::ops::DerefMut,
                 #target_bounds
+                #none_clone_bounds
             {
                 match self {
-                    #some(ref mut x) => #some(x.deref_mut()),
-                    _ => #none,
+                    #deref_mut_arms
+                    #none_ref_arms
                 }
             }
         });
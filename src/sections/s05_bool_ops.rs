@@ -4,39 +4,62 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
     #[allow(unused_variables)]
     #[rustfmt::skip]
     let DataContainer {
-        ref name, ref full_name, ref full_name_string, ref some, ref none, ref some_name, ref none_name,
-        ref some_name_snake, ref none_name_snake, ref some_ty, ref some_ty_name, is_generic, ref bounds, ref imp,
-        ref func, ref c_func, ref opt
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
     } = *container;
 
+    let none_arms = container.none_arms();
+    let x = container.some_value("x");
+    let v = container.some_value("v");
+    let some_any = container.some_pat(quote! {_});
+    let some_x = container.some_pat(quote! {x});
+
     /////////////////////////////////////////////////////////////////////////
     // Boolean operations on the values, eager and lazy
     /////////////////////////////////////////////////////////////////////////
 
+    let (u, f, p) = (
+        container.param("U"),
+        container.param("F"),
+        container.param("P"),
+    );
+
     // and
     {
+        let and = container.method("and");
         let doc = format!(
             "Returns `{none}` if the `{name}` is a `{none}`, otherwise returns `optb`. Equivalent to `Option::and`.",
             name = name, none = none_name,
         );
-        if is_generic {
-            let where_clause = container.where_clause_for(quote! {U});
+        if generic.is_some() {
+            let u_bounds = container.bounds_for(&u);
+            let u_predicates = container.predicates_for(&u);
+            let none_defaults = container.none_defaults_for(&u);
+            let name_u = container.name_with(&u);
             impl_block.extend(quote! {
                 #[doc = #doc]
-                #func and<U>(self, optb: #name<U>) -> #name<U> #where_clause {
+                #func #and<#u>(self, optb: #name_u) -> #name_u
+                where
+                    #u_bounds
+                    #u_predicates
+                    #none_defaults
+                {
                     match self {
-                        #some(_) => optb,
-                        _ => #none,
+                        #some_any => optb,
+                        #none_arms
                     }
                 }
             });
         } else {
             impl_block.extend(quote! {
                 #[doc = #doc]
-                #func and(self, optb: Self) -> Self {
+                #func #and(self, optb: Self) -> Self {
                     match self {
-                        #some(_) => optb,
-                        _ => #none,
+                        #some_any => optb,
+                        #none_arms
                     }
                 }
             });
@@ -45,35 +68,41 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // and_then
     {
+        let and_then = container.method("and_then");
         let doc = format!(
             "Returns `{name}` if the `{none}` is a `{name}`, otherwise calls `f` and returns the result. Equivalent to `Option::and_then`.",
             name = name, none = none_name,
         );
-        let u_bounds = container.bounds_for(quote! {U});
-        if is_generic {
+        let u_bounds = container.bounds_for(&u);
+        if generic.is_some() {
+            let name_u = container.name_with(&u);
+            let u_predicates = container.predicates_for(&u);
+            let none_defaults = container.none_defaults_for(&u);
             impl_block.extend(quote! {
                 #[doc = #doc]
-                #func and_then<U, F>(self, f: F) -> #name<U>
+                #func #and_then<#u, #f>(self, f: #f) -> #name_u
                 where
-                    F: FnOnce(#some_ty) -> #name<U>,
+                    #f: FnOnce(#some_ty) -> #name_u,
                     #u_bounds
+                    #u_predicates
+                    #none_defaults
                 {
                     match self {
-                        #some(x) => f(x),
-                        _ => #none,
+                        #some_x => f(#x),
+                        #none_arms
                     }
                 }
             });
         } else {
             impl_block.extend(quote! {
                 #[doc = #doc]
-                #func and_then<F>(self, f: F) -> Self
+                #func #and_then<#f>(self, f: #f) -> Self
                 where
-                    F: FnOnce(#some_ty) -> Self,
+                    #f: FnOnce(#some_ty) -> Self,
                 {
                     match self {
-                        #some(x) => f(x),
-                        _ => #none,
+                        #some_x => f(#x),
+                        #none_arms
                     }
                 }
             });
@@ -82,19 +111,34 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // filter
     {
+        let filter = container.method("filter");
         let doc = format!(
             "Returns a `{some}` if the `{name}` is a `{some}` and the contained value satisfies the predicate `pred`, otherwise returns `{none}`. Equivalent to `Option::filter`.",
             name = name, none = none_name, some = some_name,
         );
+        // keeps the `Some`-like variant
+        let filter_arms = container.some_arms(quote! {v}, |variant| {
+            let some_from_x = container.some_variant_with(variant, quote! {x});
+            quote! {{
+                let x = #v;
+                if pred(&x) {
+                    #some_from_x
+                } else {
+                    #none
+                }
+            }}
+        });
+        let none_defaults = container.none_default_bounds();
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func filter<P>(self, pred: P) -> Self
+            #func #filter<#p>(self, pred: #p) -> Self
             where
-                P: FnOnce(&#some_ty) -> bool,
+                #p: FnOnce(&#some_ty) -> bool,
+                #none_defaults
             {
                 match self {
-                    #some(x) if pred(&x) => #some(x),
-                    _ => #none,
+                    #filter_arms
+                    none => none,
                 }
             }
         });
@@ -102,15 +146,16 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // or
     {
+        let or = container.method("or");
         let doc = format!(
             "Returns the `{name}` if it is a `{some}`, otherwise returns `optb`. Equivalent to `Option::or`.",
             name = name, some = some_name,
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func or(self, optb: Self) -> Self {
+            #func #or(self, optb: Self) -> Self {
                 match self {
-                    #some(x) => #some(x),
+                    #some_any => self,
                     _ => optb,
                 }
             }
@@ -119,18 +164,19 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // or_else
     {
+        let or_else = container.method("or_else");
         let doc = format!(
             "Returns the `{name}` if it is a `{some}`, otherwise calls `f` and returns the result. Equivalent to `Option::or_else`.",
             name = name, some = some_name,
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func or_else<F>(self, f: F) -> Self
+            #func #or_else<#f>(self, f: #f) -> Self
             where
-                F: FnOnce() -> Self,
+                #f: FnOnce() -> Self,
             {
                 match self {
-                    #some(x) => #some(x),
+                    #some_any => self,
                     _ => f(),
                 }
             }
@@ -139,15 +185,27 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // xor
     {
+        let xor = container.method("xor");
         let doc = format!(
             "Returns `{some}` if exactly one of `self` or `optb` is a `{some}`, otherwise returns `{none}`. Equivalent to `Option::xor`.",
             none = none_name, some = some_name,
         );
+        // several `Some`-like variants need parentheses after `x @`, but they would trigger
+        // `unused_parens` otherwise
+        let some_any = if some_variants.len() > 1 {
+            quote! { (#some_any) }
+        } else {
+            some_any.clone()
+        };
+        let none_defaults = container.none_default_bounds();
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func xor(self, optb: Self) -> Self {
+            #func #xor(self, optb: Self) -> Self
+            where
+                #none_defaults
+            {
                 match (self, optb) {
-                    (#some(x), #none) | (#none, #some(x)) => #some(x),
+                    (x @ #some_any, #none_pat) | (#none_pat, x @ #some_any) => x,
                     _ => #none,
                 }
             }
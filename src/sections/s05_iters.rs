@@ -4,37 +4,55 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
     #[allow(unused_variables)]
     #[rustfmt::skip]
     let DataContainer {
-        ref name, ref full_name, ref full_name_string, ref some, ref none, ref some_name, ref none_name,
-        ref some_name_snake, ref none_name_snake, ref some_ty, ref some_ty_name, is_generic, ref bounds, ref imp,
-        ref func, ref c_func, ref opt
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
     } = *container;
 
+    let x = container.some_value("x");
+    let some_ref = container.some_pat(quote! {ref x});
+    let some_mut = container.some_pat(quote! {ref mut x});
+    let ref_ty = container.some_ref_ty(quote! {&});
+    let mut_ty = container.some_ref_ty(quote! {&mut});
+
     /////////////////////////////////////////////////////////////////////////
     // Iterator constructors
     /////////////////////////////////////////////////////////////////////////
 
     // iter
     {
+        let iter = container.method("iter");
         let doc = format!(
             "Returns an iterator over the possibly contained value. Equivalent to `Option::iter`.",
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func iter(&self) -> ::std::option::IntoIter<&#some_ty> {
-                self.as_option_ref().into_iter()
+            #func #iter(&self) -> ::core::option::IntoIter<#ref_ty> {
+                match *self {
+                    #some_ref => #opt::Some(#x),
+                    _ => #opt::None,
+                }
+                .into_iter()
             }
         });
     }
 
     // iter_mut
     {
+        let iter_mut = container.method("iter_mut");
         let doc = format!(
             "Returns a mutable iterator over the possibly contained value. Equivalent to `Option::iter_mut`.",
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func iter_mut(&mut self) -> ::std::option::IntoIter<&mut #some_ty> {
-                self.as_option_mut().into_iter()
+            #func #iter_mut(&mut self) -> ::core::option::IntoIter<#mut_ty> {
+                match *self {
+                    #some_mut => #opt::Some(#x),
+                    _ => #opt::None,
+                }
+                .into_iter()
             }
         });
     }
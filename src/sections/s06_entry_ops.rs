@@ -4,30 +4,45 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
     #[allow(unused_variables)]
     #[rustfmt::skip]
     let DataContainer {
-        ref name, ref full_name, ref full_name_string, ref some, ref none, ref some_name, ref none_name,
-        ref some_name_snake, ref none_name_snake, ref some_ty, ref some_ty_name, is_generic, ref bounds, ref imp,
-        ref func, ref c_func, ref opt
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
     } = *container;
 
     /////////////////////////////////////////////////////////////////////////
     // Entry-like operations to insert a value and return a reference
     /////////////////////////////////////////////////////////////////////////
 
+    // there is no `&mut #some_ty` to return if the value is made up of several fields
+    if container.is_composite() {
+        return;
+    }
+
+    let x = container.some_value("x");
+    let some_mut = container.some_pat(quote! {ref mut x});
+    let some_from_x = container.some_with(quote! {x});
+    let some_value = container.some_with(quote! {value});
+    let some_default = container.some_with(quote! {::core::default::Default::default()});
+    let some_f = container.some_with(quote! {f()});
+
     // insert
     {
+        let insert = container.method("insert");
         let doc = format!(
             "Inserts a value into the `{name}`, then returns a mutable reference to it. Equivalent to `Option::insert`.",
             name = name
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func insert(&mut self, x: #some_ty) -> &mut #some_ty {
-                *self = #some(x);
+            #func #insert(&mut self, x: #some_ty) -> &mut #some_ty {
+                *self = #some_from_x;
                 match self {
-                    #some(ref mut x) => x,
+                    #some_mut => #x,
 
                     // SAFETY: a value was just inserted.
-                    _ => unsafe { ::std::hint::unreachable_unchecked() },
+                    _ => unsafe { ::core::hint::unreachable_unchecked() },
                 }
             }
         });
@@ -35,15 +50,21 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // get_or_insert
     {
+        let get_or_insert = container.method("get_or_insert");
         let doc = format!(
             "Returns a mutable reference to the contained value, inserting the provided value if empty. Equivalent to `Option::get_or_insert`.",
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func get_or_insert(&mut self, value: #some_ty) -> &mut #some_ty {
+            #func #get_or_insert(&mut self, value: #some_ty) -> &mut #some_ty {
+                if let #none_pat = *self {
+                    *self = #some_value;
+                }
                 match self {
-                    #some(ref mut x) => x,
-                    _ => self.insert(value),
+                    #some_mut => #x,
+
+                    // SAFETY: a value was just inserted.
+                    _ => unsafe { ::core::hint::unreachable_unchecked() },
                 }
             }
         });
@@ -51,34 +72,50 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // get_or_insert_default
     {
+        let get_or_insert_default = container.method("get_or_insert_default");
         let doc = format!(
             "Returns a mutable reference to the contained value, inserting the default value if empty. Equivalent to `Option::get_or_insert_default`.",
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func get_or_insert_default(&mut self) -> &mut #some_ty
+            #func #get_or_insert_default(&mut self) -> &mut #some_ty
             where
-                #some_ty: ::std::default::Default,
+                #some_ty: ::core::default::Default,
             {
-                self.get_or_insert_with(::std::default::Default::default)
+                if let #none_pat = *self {
+                    *self = #some_default;
+                }
+                match self {
+                    #some_mut => #x,
+
+                    // SAFETY: a value was just inserted.
+                    _ => unsafe { ::core::hint::unreachable_unchecked() },
+                }
             }
         });
     }
 
     // get_or_insert_with
     {
+        let get_or_insert_with = container.method("get_or_insert_with");
+        let f = container.param("F");
         let doc = format!(
             "Returns a mutable reference to the contained value, inserting the result of the provided function if empty. Equivalent to `Option::get_or_insert_with`.",
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func get_or_insert_with<F>(&mut self, f: F) -> &mut #some_ty
+            #func #get_or_insert_with<#f>(&mut self, f: #f) -> &mut #some_ty
             where
-                F: FnOnce() -> #some_ty,
+                #f: FnOnce() -> #some_ty,
             {
+                if let #none_pat = *self {
+                    *self = #some_f;
+                }
                 match self {
-                    #some(ref mut x) => x,
-                    _ => self.insert(f()),
+                    #some_mut => #x,
+
+                    // SAFETY: a value was just inserted.
+                    _ => unsafe { ::core::hint::unreachable_unchecked() },
                 }
             }
         });
@@ -4,57 +4,97 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
     #[allow(unused_variables)]
     #[rustfmt::skip]
     let DataContainer {
-        ref name, ref full_name, ref full_name_string, ref some, ref none, ref some_name, ref none_name,
-        ref some_name_snake, ref none_name_snake, ref some_ty, ref some_ty_name, is_generic, ref bounds, ref imp,
-        ref func, ref c_func, ref opt
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
     } = *container;
 
+    let x = container.some_value("x");
+    let y = container.some_value("y");
+    let some_x = container.some_pat(quote! {x});
+    let some_y = container.some_pat(quote! {y});
+    let some_ref_y = container.some_pat(quote! {ref y});
+    let some_from_x = container.some_with(quote! {x});
+    let some_pair = container.some_with(quote! {(#x, #y)});
+    let some_fxy = container.some_with(quote! {f(#x, #y)});
+    // keep the `None`-like variant of whichever side is one, preferring `self`
+    let none_pair_arms = {
+        let conversions = container.none_conversions();
+        let left = conversions
+            .iter()
+            .map(|(pat, expr)| quote! { (#pat, _) => #expr, });
+        let right = conversions
+            .iter()
+            .map(|(pat, expr)| quote! { (_, #pat) => #expr, });
+        quote! { #(#left)* #(#right)* }
+    };
+
     /////////////////////////////////////////////////////////////////////////
     // Misc
     /////////////////////////////////////////////////////////////////////////
 
+    let (u, f, r) = (
+        container.param("U"),
+        container.param("F"),
+        container.param("R"),
+    );
+
     // take
     {
+        let take = container.method("take");
         let doc = format!(
             "Takes the actual value out of the `{name}`, leaving a `{none}` in its place. Equivalent to `Option::take`.",
             name = name, none = none_name,
         );
+        let none_defaults = container.none_default_bounds();
+        // only const with #[optional(const)], because mem::replace is not const in our MSRV
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func take(&mut self) -> Self {
-                ::std::mem::take(self)
+            #c_func #take(&mut self) -> Self where #none_defaults {
+                ::core::mem::replace(self, #none)
             }
         });
     }
 
     // replace
     {
+        let replace = container.method("replace");
         let doc = format!(
             "Replaces the actual value in the `{name}` with the provided one, returning the old value, if any. Equivalent to `Option::replace`.",
             name = name,
         );
+        // only const with #[optional(const)], because mem::replace is not const in our MSRV. A
+        // composite value is destructured into its fields, which drops the tuple and can't be const
+        let replace_func = if container.is_composite() {
+            func
+        } else {
+            c_func
+        };
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func replace(&mut self, x: #some_ty) -> Self {
-                ::std::mem::replace(self, #some(x))
+            #replace_func #replace(&mut self, x: #some_ty) -> Self {
+                ::core::mem::replace(self, #some_from_x)
             }
         });
     }
 
     // contains
-    {
+    if !container.is_composite() {
+        let contains = container.method("contains");
         let doc = format!(
             "Returns `true` if the `{name}` contains the given value. Equivalent to `Option::contains`.",
             name = name,
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func contains(&self, x: &#some_ty) -> bool
+            #func #contains(&self, x: &#some_ty) -> bool
             where
-                #some_ty: ::std::cmp::PartialEq,
+                #some_ty: ::core::cmp::PartialEq,
             {
-                match self {
-                    #some(y) => ::std::cmp::PartialEq::eq(x, y),
+                match *self {
+                    #some_ref_y => ::core::cmp::PartialEq::eq(x, #y),
                     _ => false,
                 }
             }
@@ -63,18 +103,21 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // zip
     if is_generic {
+        let zip = container.method("zip");
         let doc = format!(
             "zips `self` with another `{name}` and returns the pair of contained values if both are `{some}`s. Equivalent to `Option::zip`.",
             name = name, some = some_name,
         );
-        let where_clause = container.where_clause_for(quote! {U});
-        let tuple_bounds = container.bounds_for(quote! {(#some_ty, U)});
+        let where_clause = container.where_clause_for(&u);
+        let tuple_bounds = container.bounds_for(quote! {(#some_ty, #u)});
+        let name_u = container.name_with(&u);
+        let ret = container.name_with(quote! {(#some_ty, #u)});
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func zip<U>(self, other: #name<U>) -> #name<(#some_ty, U)> #where_clause #tuple_bounds {
+            #func #zip<#u>(self, other: #name_u) -> #ret #where_clause #tuple_bounds {
                 match (self, other) {
-                    (#some(x), #some(y)) => #some((x, y)),
-                    _ => #none,
+                    (#some_x, #some_y) => #some_pair,
+                    #none_pair_arms
                 }
             }
         });
@@ -82,24 +125,35 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // zip_with
     {
-        if is_generic {
+        let zip_with = container.method("zip_with");
+        if generic.is_some() {
             let doc = format!(
                 "zips `self` with another `{name}` and returns the result of the provided function if both are `{some}`s. Equivalent to `Option::zip_with`.",
                 name = name, some = some_name,
             );
-            let u_bounds = container.bounds_for(quote! {U});
-            let r_bounds = container.bounds_for(quote! {R});
+            let u_bounds = container.bounds_for(&u);
+            let r_bounds = container.bounds_for(&r);
+            let name_u = container.name_with(&u);
+            let name_r = container.name_with(&r);
+            let some_u = container.some_ty_with(&u);
+            let some_r = container.some_ty_with(&r);
+            let u_predicates = container.predicates_for(&u);
+            let r_predicates = container.predicates_for(&r);
+            let none_defaults = container.none_defaults_for(&r);
             impl_block.extend(quote! {
                 #[doc = #doc]
-                #func zip_with<U, F, R>(self, other: #name<U>, f: F) -> #name<R>
+                #func #zip_with<#u, #f, #r>(self, other: #name_u, f: #f) -> #name_r
                 where
-                    F: FnOnce(#some_ty, U) -> R,
+                    #f: FnOnce(#some_ty, #some_u) -> #some_r,
                     #u_bounds
                     #r_bounds
+                    #u_predicates
+                    #r_predicates
+                    #none_defaults
                 {
                     match (self, other) {
-                        (#some(x), #some(y)) => #some(f(x, y)),
-                        _ => #none,
+                        (#some_x, #some_y) => #some_fxy,
+                        #none_pair_arms
                     }
                 }
             });
@@ -110,13 +164,13 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
             );
             impl_block.extend(quote! {
                 #[doc = #doc]
-                #func zip_with<F>(self, other: Self, f: F) -> Self
+                #func #zip_with<#f>(self, other: Self, f: #f) -> Self
                 where
-                    F: FnOnce(#some_ty, #some_ty) -> #some_ty,
+                    #f: FnOnce(#some_ty, #some_ty) -> #some_ty,
                 {
                     match (self, other) {
-                        (#some(x), #some(y)) => #some(f(x, y)),
-                        _ => #none,
+                        (#some_x, #some_y) => #some_fxy,
+                        #none_pair_arms
                     }
                 }
             });
@@ -1,16 +1,26 @@
 use super::*;
+use quote::ToTokens;
 
 pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStream) {
     #[allow(unused_variables)]
     #[rustfmt::skip]
     let DataContainer {
-        ref name, ref full_name, ref full_name_string, ref some, ref none, ref some_name, ref none_name,
-        ref some_name_snake, ref none_name_snake, ref some_ty, ref some_ty_name, is_generic, ref bounds, ref imp,
-        ref func, ref c_func, ref opt
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
     } = *container;
 
+    let x = container.some_value("x");
+    let some_ref = container.some_pat(quote! {ref x});
+    let some_mut = container.some_pat(quote! {ref mut x});
+    let ref_ty = container.some_ref_ty(quote! {&});
+    let mut_ty = container.some_ref_ty(quote! {&mut});
+
     // as_option_ref
     {
+        let as_option_ref = container.method("as_option_ref");
         let doc = format!(
             "Converts from `&{name}` to `Option<&{ty}>`.",
             name = full_name_string,
@@ -18,9 +28,9 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
         );
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func as_option_ref(&self) -> #opt<&#some_ty> {
+            #c_func #as_option_ref(&self) -> #opt<#ref_ty> {
                 match *self {
-                    #some(ref x) => #opt::Some(x),
+                    #some_ref => #opt::Some(#x),
                     _ => #opt::None,
                 }
             }
@@ -29,19 +39,133 @@ pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStrea
 
     // as_option_mut
     {
+        let as_option_mut = container.method("as_option_mut");
         let doc = format!(
             "Converts from `&mut {name}` to `Option<&mut {ty}>`.",
             name = full_name_string,
             ty = some_ty_name,
         );
+        // only const with #[optional(const)], because &mut in const functions isn't available in our MSRV
         impl_block.extend(quote! {
             #[doc = #doc]
-            #func as_option_mut(&mut self) -> #opt<&mut #some_ty> {
+            #c_func #as_option_mut(&mut self) -> #opt<#mut_ty> {
                 match *self {
-                    #some(ref mut x) => #opt::Some(x),
+                    #some_mut => #opt::Some(#x),
                     _ => #opt::None,
                 }
             }
         });
     }
+
+    let (member, reason_ty) = match container.reason_field() {
+        Some(reason) => reason,
+        None => return,
+    };
+    let reason_ty_name = reason_ty.to_token_stream().to_string();
+    let some_x = container.some_pat(quote! {x});
+    let reason_r = container.reason_pat(&member, quote! {r});
+    let reason_ref = container.reason_pat(&member, quote! {ref r});
+    let reason_mut = container.reason_pat(&member, quote! {ref mut r});
+    let f = container.param("F");
+    // none of these are c_func, since a reason always disables const, see `is_const`
+
+    // reason
+    {
+        let reason = container.method("reason");
+        let doc = format!(
+            "Returns a reference to the reason in `{none}`, or `None` if the value is a `{some}`.",
+            some = some_name,
+            none = none_name,
+        );
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #reason(&self) -> #opt<&#reason_ty> {
+                match *self {
+                    #reason_ref => #opt::Some(r),
+                    _ => #opt::None,
+                }
+            }
+        });
+    }
+
+    // reason_mut
+    {
+        let reason_mut_fn = container.method("reason_mut");
+        let doc = format!(
+            "Returns a mutable reference to the reason in `{none}`, or `None` if the value is a `{some}`.",
+            some = some_name,
+            none = none_name,
+        );
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #reason_mut_fn(&mut self) -> #opt<&mut #reason_ty> {
+                match *self {
+                    #reason_mut => #opt::Some(r),
+                    _ => #opt::None,
+                }
+            }
+        });
+    }
+
+    // into_reason
+    {
+        let into_reason = container.method("into_reason");
+        let doc = format!(
+            "Returns the reason in `{none}`, consuming `self`, or `None` if the value is a `{some}`.",
+            some = some_name,
+            none = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #into_reason(self) -> #opt<#reason_ty> {
+                match self {
+                    #reason_r => #opt::Some(r),
+                    _ => #opt::None,
+                }
+            }
+        });
+    }
+
+    // unwrap_or_else_reason
+    {
+        let unwrap_or_else_reason = container.method("unwrap_or_else_reason");
+        let doc = format!(
+            "Returns the contained `{some}` value or computes it from the reason in `{none}`. Like `Option::unwrap_or_else`, but the closure receives the reason.",
+            some = some_name,
+            none = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #unwrap_or_else_reason<#f>(self, f: #f) -> #some_ty
+            where
+                #f: FnOnce(#reason_ty) -> #some_ty,
+            {
+                match self {
+                    #some_x => #x,
+                    #reason_r => f(r),
+                }
+            }
+        });
+    }
+
+    // ok_or_reason
+    {
+        let ok_or_reason = container.method("ok_or_reason");
+        let doc = format!(
+            "Transforms the `{name}` into a `Result<{ty}, {reason}>`, mapping `{some}(x)` to `Ok(x)` and `{none}(reason)` to `Err(reason)`.",
+            name = full_name_string, ty = some_ty_name, reason = reason_ty_name, some = some_name, none = none_name,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #ok_or_reason(self) -> ::core::result::Result<#some_ty, #reason_ty> {
+                match self {
+                    #some_x => ::core::result::Result::Ok(#x),
+                    #reason_r => ::core::result::Result::Err(r),
+                }
+            }
+        });
+    }
 }
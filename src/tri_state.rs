@@ -0,0 +1,151 @@
+//! `#[derive(TriState)]`, which reuses the `DataContainer` of `Optional`: The `Defined`-like
+//! variant is the `Some`-like variant, and the `Null`-like and `Undefined`-like variants are two
+//! `None`-like variants, with the `Undefined`-like one as the default.
+
+use super::*;
+use attributes::{parse_tri_state_marker, TriStateMarker};
+use external::TraitImpl;
+
+mod s01_querying;
+mod s02_transformers;
+mod traits;
+
+pub(crate) fn tri_state_internal(input: syn::DeriveInput) -> Result<TokenStream> {
+    let attrs = Attributes::parse(&input.attrs, "tri_state")?;
+    let name = input.ident;
+
+    let data = match input.data {
+        syn::Data::Enum(data) => Ok(data),
+        syn::Data::Struct(data) => Err(data.struct_token.span),
+        syn::Data::Union(data) => Err(data.union_token.span),
+    }
+    .map_err(|span| Error::new(span, "TriState can only be used on enums"))?;
+
+    let variants = data.variants;
+    check_default_conflict(
+        &input.attrs,
+        &variants,
+        &attrs,
+        "TriState",
+        "`Undefined`-like",
+    )?;
+
+    if variants.len() != 3 {
+        let msg = "TriState only works when there are exactly 3 enum variants";
+        return Error::err_spanned(variants, msg);
+    }
+
+    let mut error = Error::builder();
+    let mut defined = vec![];
+    let mut null = vec![];
+    let mut undefined = vec![];
+    let mut unmarked = vec![];
+    for variant in variants {
+        let marker = parse_tri_state_marker(&variant.attrs).unwrap_or_else(|e| {
+            error.push(e.into());
+            None
+        });
+        match marker {
+            Some(_) if !is_unit_like(&variant.fields) => {
+                let msg = "Only the variants without data can be marked as `null` or `undefined`";
+                error.with_spanned(&variant, msg);
+            }
+            Some(TriStateMarker::Null) => null.push(variant),
+            Some(TriStateMarker::Undefined) => undefined.push(variant),
+            None if is_unit_like(&variant.fields) => unmarked.push(variant),
+            None => defined.push(variant),
+        }
+    }
+    error.ok_or_build()?;
+
+    if defined.len() != 1 {
+        let msg = "TriState needs exactly one variant with data (the `Defined(T)` equivalent). \
+                   The other two variants can only contain `PhantomData`";
+        let mut error = Error::builder();
+        if defined.is_empty() {
+            for variant in null.iter().chain(&undefined).chain(&unmarked) {
+                error.with_spanned(variant, msg);
+            }
+        } else {
+            for variant in defined.iter() {
+                error.with_spanned(variant, msg);
+            }
+        }
+        return error.build_err();
+    }
+    // the names of the variants don't tell which one is `Null` and which one is `Undefined`
+    let mut error = Error::builder();
+    for variant in unmarked.iter() {
+        let msg = "The variants without data need to be marked with `#[tri_state(null)]` or \
+                   `#[tri_state(undefined)]`";
+        error.with_spanned(variant, msg);
+    }
+    for (marked, key) in [(&null, "null"), (&undefined, "undefined")].iter() {
+        for variant in marked.iter().skip(1) {
+            let msg = format!("Only one variant can be marked with `{}`", key);
+            error.with_spanned(variant, msg);
+        }
+    }
+    error.ok_or_build()?;
+
+    let vis = input.vis;
+    // the `None`-like variants are `Null` and `Undefined`, in that order
+    let empty = null.into_iter().chain(undefined).collect();
+    let mut container =
+        DataContainer::new(name, vis.clone(), input.generics, attrs, defined, empty, 1)?;
+    let vis = container.attrs.vis().unwrap_or(&vis).clone();
+    container.set_visibility(vis);
+
+    let mut impl_block = TokenStream::new();
+    let mut additional_impls = TokenStream::new();
+
+    s01_querying::add_section(&container, &mut impl_block);
+    s02_transformers::add_section(&container, &mut impl_block);
+    traits::add_external(&container, &mut additional_impls);
+
+    container.attrs.check_unused_renames()?;
+
+    let DataContainer {
+        full_name,
+        imp,
+        imp_where,
+        krate,
+        ..
+    } = container;
+
+    Ok(quote! {
+        #[allow(dead_code)]
+        #imp #full_name #imp_where {
+            #impl_block
+        }
+
+        #additional_impls
+
+        // fails to compile if the path set with `crate = "..."` does not resolve
+        const _: () = {
+            #[allow(unused_imports)]
+            use #krate as _;
+        };
+    })
+}
+
+impl DataContainer {
+    /// The `Null`-like variant
+    fn null_variant(&self) -> &NoneVariant {
+        &self.none_variants[0]
+    }
+    /// The `Undefined`-like variant
+    fn undefined_variant(&self) -> &NoneVariant {
+        &self.none_variants[1]
+    }
+    /// A pattern that matches `variant`, ignoring any `PhantomData` in it
+    fn empty_pat(&self, variant: &NoneVariant) -> TokenStream {
+        let name = &self.name;
+        let ident = &variant.ident;
+        quote! { #name::#ident { .. } }
+    }
+    /// An expression that creates `variant`
+    fn empty_with(&self, variant: &NoneVariant) -> TokenStream {
+        none_with_defaults(&self.name, &variant.ident, &variant.fields)
+    }
+}
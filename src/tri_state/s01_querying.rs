@@ -0,0 +1,63 @@
+use super::*;
+use syn::Ident;
+
+pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStream) {
+    #[allow(unused_variables)]
+    #[rustfmt::skip]
+    let DataContainer {
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
+    } = *container;
+
+    let some_any = container.some_pat(quote! {_});
+
+    /////////////////////////////////////////////////////////////////////////
+    // Querying the contained values
+    /////////////////////////////////////////////////////////////////////////
+
+    // is_defined
+    {
+        let is_defined = Ident::new(
+            &attrs.method_name("is_defined", &format!("is_{}", some_name_snake)),
+            some.clone().into_iter().last().unwrap().span(),
+        );
+        let doc = format!(
+            "Returns `true` if the `{name}` is a `{defined}` value.",
+            name = name,
+            defined = some_name,
+        );
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #c_func #is_defined(&self) -> bool {
+                ::core::matches!(*self, #some_any)
+            }
+        });
+    }
+
+    // is_null, is_undefined
+    for (key, variant) in [
+        ("is_null", container.null_variant()),
+        ("is_undefined", container.undefined_variant()),
+    ] {
+        let snake = variant.ident.to_string().to_case(Case::Snake);
+        let method = Ident::new(
+            &attrs.method_name(key, &format!("is_{}", snake)),
+            variant.ident.span(),
+        );
+        let doc = format!(
+            "Returns `true` if the `{name}` is a `{variant}` value.",
+            name = name,
+            variant = variant.ident,
+        );
+        let pat = container.empty_pat(variant);
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #c_func #method(&self) -> bool {
+                ::core::matches!(*self, #pat)
+            }
+        });
+    }
+}
@@ -0,0 +1,98 @@
+use super::*;
+
+pub(crate) fn add_section(container: &DataContainer, impl_block: &mut TokenStream) {
+    #[allow(unused_variables)]
+    #[rustfmt::skip]
+    let DataContainer {
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
+    } = *container;
+
+    let none_arms = container.none_arms();
+    let x = container.some_value("x");
+    let some_x = container.some_pat(quote! {x});
+    // these keep the `Defined`-like variant
+    let fx_arms = container.some_arms(quote! {x}, |v| {
+        container.some_variant_with(v, quote! {f(#x)})
+    });
+    let null = &container.null_variant().ident;
+    let null_pat = container.empty_pat(container.null_variant());
+    let undefined = &container.undefined_variant().ident;
+    let undefined_pat = container.empty_pat(container.undefined_variant());
+
+    /////////////////////////////////////////////////////////////////////////
+    // Transforming contained values
+    /////////////////////////////////////////////////////////////////////////
+
+    let (u, f) = (container.param("U"), container.param("F"));
+
+    // into_option
+    {
+        let into_option = container.method("into_option");
+        let doc = format!(
+            "Converts the `{name}` to an `Option`, treating both `{null}` and `{undefined}` as `None`.",
+            name = name, null = null, undefined = undefined,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #into_option(self) -> #opt<#some_ty> {
+                match self {
+                    #some_x => #opt::Some(#x),
+                    _ => #opt::None,
+                }
+            }
+        });
+    }
+
+    // map
+    if let Some(generic) = generic {
+        let map = container.method("map");
+        let doc = format!(
+            "Maps a `{name}<{ty}>` to `{name}<U>` by applying a function to a contained value, keeping `{null}` and `{undefined}`.",
+            name = name, ty = generic, null = null, undefined = undefined,
+        );
+        let u_bounds = container.bounds_for(&u);
+        let ret = container.name_with(&u);
+        let some_u = container.some_ty_with(&u);
+        let u_predicates = container.predicates_for(&u);
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #map<#u, #f>(self, f: #f) -> #ret
+            where
+                #f: FnOnce(#some_ty) -> #some_u,
+                #u_bounds
+                #u_predicates
+            {
+                match self {
+                    #fx_arms
+                    #none_arms
+                }
+            }
+        });
+    }
+
+    // merge_into
+    {
+        let merge_into = container.method("merge_into");
+        let doc = format!(
+            "Applies the `{name}` to `target` like a patch: A `{defined}` value replaces it, `{null}` clears it and `{undefined}` leaves it unchanged.",
+            name = name, defined = some_name, null = null, undefined = undefined,
+        );
+        // can't be c_func right now because of destructors (https://github.com/rust-lang/rust/issues/67792)
+        impl_block.extend(quote! {
+            #[doc = #doc]
+            #func #merge_into(self, target: &mut #opt<#some_ty>) {
+                match self {
+                    #some_x => *target = #opt::Some(#x),
+                    #null_pat => *target = #opt::None,
+                    #undefined_pat => {}
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+pub(crate) fn add_external(container: &DataContainer, additional_impls: &mut TokenStream) {
+    #[allow(unused_variables)]
+    #[rustfmt::skip]
+    let DataContainer {
+        ref name, ref full_name, ref full_name_string, ref some, ref none, ref none_pat, ref some_name,
+        ref none_name, ref some_name_snake, ref none_name_snake, ref some_variants,
+        ref none_variants, ref some_ty, ref some_ty_name, is_generic, ref generic, ref bounds,
+        ref generics, ref imp, ref imp_where, ref vis, ref func, ref c_func, ref opt, ref krate,
+        ref attrs
+    } = *container;
+
+    let x = container.some_value("x");
+    let some_x = container.some_pat(quote! {x});
+    let some_from_x = container.some_with(quote! {x});
+    let null = container.empty_with(container.null_variant());
+    let null_pat = container.empty_pat(container.null_variant());
+    let undefined = container.empty_with(container.undefined_variant());
+    let undefined_pat = container.empty_pat(container.undefined_variant());
+
+    // Self: From<Option<Option>>
+    if attrs.implements(TraitImpl::From) {
+        let doc = format!(
+            "Converts `None` to `{undefined}`, `Some(None)` to `{null}` and `Some(Some(x))` to `{defined}(x)`.",
+            undefined = container.undefined_variant().ident,
+            null = container.null_variant().ident,
+            defined = some_name,
+        );
+        additional_impls.extend(quote! {
+            #[automatically_derived]
+            #imp ::core::convert::From<#opt<#opt<#some_ty>>> for #full_name #imp_where {
+                #[doc = #doc]
+                fn from(src: #opt<#opt<#some_ty>>) -> Self {
+                    match src {
+                        #opt::Some(#opt::Some(x)) => #some_from_x,
+                        #opt::Some(#opt::None) => #null,
+                        #opt::None => #undefined,
+                    }
+                }
+            }
+        });
+    }
+
+    // Option<Option>: From<Self>
+    if attrs.implements(TraitImpl::From) {
+        additional_impls.extend(quote! {
+            #[automatically_derived]
+            #imp ::core::convert::From<#full_name> for #opt<#opt<#some_ty>> #imp_where {
+                fn from(src: #full_name) -> Self {
+                    match src {
+                        #some_x => #opt::Some(#opt::Some(#x)),
+                        #null_pat => #opt::Some(#opt::None),
+                        #undefined_pat => #opt::None,
+                    }
+                }
+            }
+        });
+    }
+
+    // Self: Default
+    if attrs.implements(TraitImpl::Default) {
+        let doc = format!(
+            "Returns an `{}` value.",
+            container.undefined_variant().ident,
+        );
+        additional_impls.extend(quote! {
+            #[automatically_derived]
+            #imp ::core::default::Default for #full_name #imp_where {
+                #[doc = #doc]
+                fn default() -> Self {
+                    #undefined
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,35 @@
+use derive_optional::Optional;
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Slot<T, const N: usize> {
+    Filled([T; N]),
+    Empty,
+}
+
+// `F` is also the name of the generic parameter of a generated method
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Chunk<const F: usize> {
+    Bytes([u8; F]),
+    Nothing,
+}
+
+#[test]
+fn array_payload() {
+    let mut slot = Slot::Filled([1, 2, 3]);
+    assert!(slot.is_filled());
+    assert_eq!(slot.map_or(0, |a| a.iter().sum()), 6);
+    assert_eq!(slot.take(), Slot::Filled([1, 2, 3]));
+    assert_eq!(slot, Slot::Empty);
+    assert_eq!(slot.unwrap_or([0; 3]), [0, 0, 0]);
+
+    let slot: Slot<u8, 2> = Some([4, 5]).into();
+    assert_eq!(slot.unwrap(), [4, 5]);
+}
+
+#[test]
+fn no_name_collisions() {
+    let chunk = Chunk::Bytes([1, 2]);
+    assert_eq!(chunk.map_or_else(|| 0, |b| b.len()), 2);
+    assert_eq!(Chunk::<4>::Nothing.unwrap_or_else(|| [9; 4]), [9; 4]);
+    assert_eq!(chunk.ok_or_else(|| "nothing"), Ok([1, 2]));
+}
@@ -0,0 +1,51 @@
+use derive_optional::Optional;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashSet;
+use std::hash::{BuildHasher, BuildHasherDefault};
+
+type Hasher = BuildHasherDefault<DefaultHasher>;
+
+#[derive(Optional, Debug, Clone)]
+enum Handle<S: BuildHasher> {
+    Set(HashSet<u32, S>),
+    Empty,
+}
+
+// The names of the enum's generics are also used for the generics of some methods
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Pair<U, F>
+where
+    F: Copy,
+{
+    Both((U, F)),
+    Neither,
+}
+
+#[test]
+fn passes_through() {
+    let mut set = HashSet::with_hasher(Hasher::default());
+    set.insert(1);
+    let handle = Handle::Set(set);
+    assert!(handle.is_set());
+    assert!(handle.contains(&handle.clone().unwrap()));
+    assert!(handle.unwrap().contains(&1));
+
+    let empty: Handle<Hasher> = Handle::Empty;
+    assert!(empty.is_empty());
+    assert_eq!(empty.unwrap_or_default().len(), 0);
+}
+
+#[test]
+fn no_name_collisions() {
+    let both: Pair<u8, i8> = Pair::Both((1, -1));
+    assert_eq!(both.map_or(0, |(u, f)| u as i32 + f as i32 * 2), -1);
+    assert_eq!(both.ok_or("neither"), Ok((1, -1)));
+    assert_eq!(both.filter(|(u, _)| *u > 1), Pair::Neither);
+
+    let neither: Pair<u8, i8> = Pair::Neither;
+    assert_eq!(neither.unwrap_or_else(|| (2, -2)), (2, -2));
+    assert_eq!(
+        neither.zip_with(both, |(a, b), (c, d)| (a + c, b + d)),
+        Pair::Neither
+    );
+}
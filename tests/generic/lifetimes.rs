@@ -0,0 +1,38 @@
+use derive_optional::Optional;
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum MaybeRef<'a, T> {
+    Ref(&'a T),
+    Nothing,
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Token<'a, 'b: 'a>
+where
+    'b: 'a,
+{
+    Word(&'a &'b str),
+    End,
+}
+
+#[test]
+fn borrowed_payload() {
+    let x = 5;
+    let some = MaybeRef::Ref(&x);
+    assert!(some.is_ref());
+    assert_eq!(some.unwrap(), &5);
+    assert_eq!(some.map_or(0, |x| *x * 2), 10);
+
+    let none: MaybeRef<usize> = MaybeRef::Nothing;
+    assert_eq!(none.unwrap_or(&1), &1);
+    assert_eq!(Option::<&usize>::from(none), None);
+}
+
+#[test]
+fn multiple_lifetimes() {
+    let word = "word";
+    let mut token = Token::Word(&word);
+    assert_eq!(token.take(), Token::Word(&"word"));
+    assert_eq!(token, Token::End);
+    assert_eq!(**token.get_or_insert(&"other"), "other");
+}
@@ -0,0 +1,58 @@
+use derive_optional::Optional;
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone)]
+enum Batch<T> {
+    Items(Vec<T>),
+    Empty,
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum MaybeRef<'a, T> {
+    Ref(&'a T),
+    Nothing,
+}
+
+#[derive(Optional, Debug, PartialEq, Eq)]
+enum Nested<T: Clone> {
+    Boxed(Box<Option<(T, usize)>>),
+    Missing,
+}
+
+#[test]
+fn map() {
+    let batch = Batch::Items(vec![1, 2]);
+    let strings = batch.map(|v| v.into_iter().map(|x| x.to_string()).collect());
+    assert_eq!(strings, Batch::Items(vec!["1".to_owned(), "2".to_owned()]));
+
+    let empty: Batch<u8> = Batch::Empty;
+    assert_eq!(empty.map(|v| vec![v.len()]), Batch::Empty);
+
+    let x = 1;
+    let y = 2;
+    assert_eq!(MaybeRef::Ref(&x).map(|_| &y), MaybeRef::Ref(&2));
+
+    let nested = Nested::Boxed(Box::new(Some(('a', 1))));
+    let mapped = nested.map(|b| Box::new(b.map(|(c, n)| (c.to_string(), n + 1))));
+    assert_eq!(mapped, Nested::Boxed(Box::new(Some(("a".to_owned(), 2)))));
+}
+
+#[test]
+fn and() {
+    let batch = Batch::Items(vec![1]);
+    assert_eq!(
+        batch.clone().and(Batch::Items(vec!['a'])),
+        Batch::Items(vec!['a'])
+    );
+    assert_eq!(
+        batch.and_then(|v| Batch::Items(vec![!v.is_empty()])),
+        Batch::Items(vec![true])
+    );
+}
+
+#[test]
+fn zip_with() {
+    let numbers = Batch::Items(vec![1, 2]);
+    let chars = Batch::Items(vec!['a', 'b']);
+    let zipped = numbers.zip_with(chars, |a, b| a.into_iter().zip(b).collect());
+    assert_eq!(zipped, Batch::Items(vec![(1, 'a'), (2, 'b')]));
+}
@@ -0,0 +1,60 @@
+use derive_optional::Optional;
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone)]
+enum Iterable<T>
+where
+    for<'a> &'a T: IntoIterator,
+{
+    Has(T),
+    Nothing,
+}
+
+#[derive(Optional, Debug, Clone)]
+enum Items<I: Iterator>
+where
+    I::Item: Clone,
+    Vec<I>: Clone,
+{
+    Some(Vec<I>),
+    Nothing,
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Unrelated<T, S>
+where
+    S: Default,
+    PhantomData<S>: Copy,
+    T: Copy,
+{
+    Value((T, PhantomData<S>)),
+    Nothing,
+}
+
+#[test]
+fn higher_ranked() {
+    let has = Iterable::Has(vec![2, 1]);
+    assert_eq!(has.as_option_ref().map(|v| v.len()), Some(2));
+    let set = has.map(|v| v.into_iter().collect::<BTreeSet<_>>());
+    assert_eq!(set.unwrap().into_iter().collect::<Vec<_>>(), vec![1, 2]);
+
+    let nothing: Iterable<Vec<u8>> = Iterable::Nothing;
+    assert_eq!(nothing.and(Iterable::Has([1])), Iterable::Nothing);
+}
+
+#[test]
+fn associated_types() {
+    let items = Items::Some(vec![vec![1u8, 2].into_iter()]);
+    let mapped = items.map(|v| v.into_iter().map(|i| i.rev()).collect());
+    let flat = mapped.unwrap().into_iter().flatten().collect::<Vec<_>>();
+    assert_eq!(flat, vec![2, 1]);
+}
+
+#[test]
+fn unrelated_predicates() {
+    let value: Unrelated<u8, u16> = Unrelated::Value((1, PhantomData));
+    assert!(value.is_value());
+    assert_eq!(value.unwrap().0, 1);
+    assert_eq!(value.filter(|(x, _)| *x == 1).map_or(0, |(x, _)| x), 1);
+}
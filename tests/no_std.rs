@@ -0,0 +1,42 @@
+//! Makes sure that the generated code does not rely on `std`, since `::std` can't be resolved here
+
+#![no_std]
+
+use derive_optional::Optional;
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum NoStd<T> {
+    Something(T),
+    Nothing,
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum NoStdFixed {
+    Something(usize),
+    Nothing,
+}
+
+#[test]
+fn generic() {
+    let mut test = NoStd::Something(1);
+    assert!(test.is_something());
+    assert_eq!(test.map(|x| x + 1), NoStd::Something(2));
+    assert_eq!(test.as_mut().unwrap(), &mut 1);
+    assert_eq!(test.iter().next(), Some(&1));
+    assert_eq!(test.take(), NoStd::Something(1));
+    assert_eq!(test.get_or_insert_default(), &mut 0);
+    assert_eq!(test.ok_or(()), Ok(0));
+
+    let option: Option<usize> = NoStd::Nothing.into();
+    assert_eq!(option, None);
+    assert_eq!(NoStd::<usize>::default(), NoStd::Nothing);
+}
+
+#[test]
+fn fixed() {
+    let test = NoStdFixed::Something(1);
+    assert_eq!(test.as_slice(), &[1]);
+    assert_eq!(test.xor(NoStdFixed::Nothing), test);
+    assert_eq!(test.into_iter().next(), Some(1));
+    assert_eq!(test.unwrap_or_default(), 1);
+}
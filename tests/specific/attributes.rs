@@ -0,0 +1,301 @@
+use derive_optional::Optional;
+
+// only
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(only(querying, get_contained))]
+enum OnlyType {
+    Something(usize),
+    Nothing,
+}
+
+impl OnlyType {
+    // would conflict if `transformers` was generated
+    fn map(self) -> usize {
+        42
+    }
+}
+
+#[test]
+fn only() {
+    let test = OnlyType::Something(1);
+    assert!(test.is_something());
+    assert_eq!(test.unwrap(), 1);
+    assert_eq!(test.map(), 42);
+}
+
+// skip
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(skip(entry_ops, misc, additions))]
+enum SkipType {
+    Something(usize),
+    Nothing,
+}
+
+impl SkipType {
+    // would conflict if `misc` was generated
+    fn take(&mut self) -> usize {
+        42
+    }
+    // would conflict if `additions` was generated
+    fn as_option_ref(&self) -> usize {
+        43
+    }
+}
+
+#[test]
+fn skip() {
+    let mut test = SkipType::Something(1);
+    assert_eq!(test.take(), 42);
+    assert_eq!(test.as_option_ref(), 43);
+    assert_eq!(test.iter().next(), Some(&1));
+    assert_eq!(test, SkipType::Something(1));
+}
+
+// skip on the methods in separate impl blocks
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(skip(transformers))]
+enum SkipNestedType<T> {
+    Something(T),
+    Nothing,
+}
+
+impl<T> SkipNestedType<&T> {
+    // would conflict if `transformers` was generated
+    fn copied(self) -> usize {
+        42
+    }
+    fn cloned(self) -> usize {
+        43
+    }
+}
+
+#[test]
+fn skip_nested() {
+    let test = SkipNestedType::Something(1);
+    assert_eq!(test.as_ref().copied(), 42);
+    assert_eq!(test.as_ref().cloned(), 43);
+}
+
+// no_impl
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(no_impl(Default, From))]
+enum NoImplType {
+    Something(usize),
+    Nothing,
+}
+
+impl Default for NoImplType {
+    fn default() -> Self {
+        NoImplType::Nothing
+    }
+}
+
+impl From<usize> for NoImplType {
+    fn from(x: usize) -> Self {
+        if x == 0 {
+            NoImplType::Nothing
+        } else {
+            NoImplType::Something(x)
+        }
+    }
+}
+
+#[test]
+fn no_impl() {
+    assert_eq!(NoImplType::default(), NoImplType::Nothing);
+    assert_eq!(NoImplType::from(0), NoImplType::Nothing);
+    assert_eq!(NoImplType::from(1), NoImplType::Something(1));
+
+    // still generated
+    assert_eq!(NoImplType::Something(1).into_iter().next(), Some(1));
+
+    // does not rely on the Default implementation
+    let mut test = NoImplType::Something(1);
+    assert_eq!(test.take(), NoImplType::Something(1));
+    assert_eq!(test, NoImplType::Nothing);
+}
+
+// rename
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(rename(
+    is_some = "has_changed",
+    is_none = "is_unchanged",
+    unwrap = "unwrap_changed"
+))]
+enum RenameType<T> {
+    Changed(T),
+    NoChange,
+}
+
+#[test]
+fn rename() {
+    let test = RenameType::Changed(1);
+    assert!(test.has_changed());
+    assert!(!test.is_unchanged());
+    assert_eq!(test.unwrap_changed(), 1);
+    assert_eq!(test.map(|x| x + 1), RenameType::Changed(2));
+
+    let test = RenameType::<usize>::NoChange;
+    assert!(!test.has_changed());
+    assert!(test.is_unchanged());
+}
+
+// rename_all
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(rename_all(prefix = "opt_", suffix = "_x"), rename(insert = "put"))]
+enum RenameAllType {
+    Something(usize),
+    Nothing,
+}
+
+#[test]
+fn rename_all() {
+    let mut test = RenameAllType::Nothing;
+    assert!(test.opt_is_nothing_x());
+    assert_eq!(test.opt_get_or_insert_with_x(|| 1), &mut 1);
+    assert_eq!(test.put(2), &mut 2);
+    assert_eq!(test.opt_unwrap_x(), 2);
+}
+
+// vis
+mod vis_inner {
+    use derive_optional::Optional;
+
+    #[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+    #[optional(vis = "pub(crate)", vis(querying = "pub(super)", misc = ""))]
+    pub enum VisType {
+        Something(usize),
+        Nothing,
+    }
+
+    pub fn take_inner(test: &mut VisType) -> VisType {
+        test.take()
+    }
+}
+use vis_inner::{take_inner, VisType};
+
+#[test]
+fn vis() {
+    let mut test = VisType::Something(1);
+    assert!(test.is_something());
+    assert_eq!(test.unwrap(), 1);
+    assert_eq!(take_inner(&mut test), VisType::Something(1));
+    assert!(test.is_nothing());
+}
+
+// private enums don't warn about unused methods
+#[derive(Optional)]
+enum PrivateType {
+    Something(usize),
+    Nothing,
+}
+
+#[test]
+fn private() {
+    assert!(PrivateType::Something(1).is_something());
+    assert!(PrivateType::Nothing.is_nothing());
+}
+
+// unwrap_msg
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(unwrap_msg = "no change was recorded")]
+enum UnwrapMsgType {
+    Something(usize),
+    Nothing,
+}
+
+#[test]
+#[should_panic(expected = "no change was recorded")]
+fn unwrap_msg() {
+    UnwrapMsgType::Nothing.unwrap();
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(rename(unwrap = "unwrap_renamed"))]
+enum UnwrapRenamedType {
+    Something(usize),
+    Nothing,
+}
+
+#[test]
+#[should_panic(expected = "called `UnwrapRenamedType::unwrap_renamed()` on a `Nothing` value")]
+fn unwrap_msg_renamed() {
+    UnwrapRenamedType::Nothing.unwrap_renamed();
+}
+
+// crate
+mod re_export {
+    pub use derive_optional::Optional as ReExported;
+}
+
+#[derive(re_export::ReExported, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(crate = "crate::specific::attributes::re_export")]
+enum CrateType {
+    Something(usize),
+    Nothing,
+}
+
+#[test]
+fn krate() {
+    assert_eq!(CrateType::Something(1).unwrap(), 1);
+    assert_eq!(CrateType::default(), CrateType::Nothing);
+}
+
+// const
+// requires a newer Rust version than our MSRV
+#[cfg(not(msrv_build))]
+mod const_fn {
+    #![allow(clippy::incompatible_msrv)]
+    use derive_optional::Optional;
+
+    #[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+    #[optional(const)]
+    enum ConstType<T> {
+        Something(T),
+        Nothing,
+    }
+
+    const CONST_SOME: ConstType<u8> = ConstType::Something(1);
+    const CONST_NONE: ConstType<u8> = ConstType::Nothing;
+
+    const IS_SOME: bool = CONST_SOME.is_something();
+    const IS_NONE: bool = CONST_NONE.is_nothing();
+    const AS_OPTION_REF: Option<&u8> = CONST_SOME.as_option_ref();
+    const AS_REF: ConstType<&u8> = CONST_SOME.as_ref();
+    const AS_SLICE: &[u8] = CONST_SOME.as_slice();
+    const UNWRAP: u8 = CONST_SOME.unwrap();
+    const EXPECT: u8 = CONST_SOME.expect("is something");
+    const UNWRAP_UNCHECKED: u8 = unsafe { CONST_SOME.unwrap_unchecked() };
+    const AS_MUT: u8 = {
+        let mut test = CONST_NONE;
+        let _ = test.replace(2);
+        if let ConstType::Something(x) = test.as_mut() {
+            *x += 1;
+        }
+        test.take().unwrap()
+    };
+
+    #[test]
+    fn const_fn() {
+        assert!(IS_SOME);
+        assert!(IS_NONE);
+        assert_eq!(AS_OPTION_REF, Some(&1));
+        assert_eq!(AS_REF, ConstType::Something(&1));
+        assert_eq!(AS_SLICE, &[1]);
+        assert_eq!(UNWRAP, 1);
+        assert_eq!(EXPECT, 1);
+        assert_eq!(UNWRAP_UNCHECKED, 1);
+        assert_eq!(AS_MUT, 3);
+
+        // still works at runtime, including with types that need to be dropped
+        let test = ConstType::Something(String::from("test"));
+        assert_eq!(test.as_ref().unwrap(), "test");
+        assert_eq!(test.unwrap(), "test");
+    }
+
+    #[test]
+    #[should_panic(expected = "called `ConstType::unwrap()` on a `Nothing` value")]
+    fn const_fn_panic() {
+        ConstType::<String>::Nothing.unwrap();
+    }
+}
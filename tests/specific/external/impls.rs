@@ -1,8 +1,182 @@
-// use derive_optional::Optional;
-
-// #[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
-// enum TestType {
-//     Something(usize),
-//     Nothing,
-// }
-// use TestType::*;
+use derive_optional::Optional;
+use std::marker::PhantomData;
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone)]
+enum TestType<T> {
+    Something(T),
+    Nothing,
+}
+use TestType::*;
+
+// copied on #name<&#some_ty>
+#[test]
+fn copied_ref() {
+    let test = Something(1);
+    assert_eq!(test.as_ref().copied(), Something(1));
+    assert_eq!(TestType::<&u8>::Nothing.copied(), Nothing);
+}
+
+// cloned on #name<&#some_ty>
+#[test]
+fn cloned_ref() {
+    let test = Something(String::from("a"));
+    assert_eq!(test.as_ref().cloned(), test);
+    assert_eq!(TestType::<&String>::Nothing.cloned(), Nothing);
+}
+
+// copied on #name<&mut #some_ty>
+#[test]
+fn copied_mut() {
+    let mut test = Something(1);
+    let copy = test.as_mut().copied();
+    if let Something(x) = test.as_mut() {
+        *x = 2;
+    }
+    assert_eq!(copy, Something(1));
+    assert_eq!(test, Something(2));
+}
+
+// cloned on #name<&mut #some_ty>
+#[test]
+fn cloned_mut() {
+    let mut test = Something(vec![1]);
+    assert_eq!(test.as_mut().cloned(), Something(vec![1]));
+    assert_eq!(TestType::<&mut Vec<u8>>::Nothing.cloned(), Nothing);
+}
+
+#[derive(Optional, Debug, PartialEq, Eq)]
+enum Bounded<'a, T: std::fmt::Debug, Tag>
+where
+    T: Clone,
+{
+    Value(T, #[optional(skip)] PhantomData<&'a Tag>),
+    Missing(PhantomData<Tag>),
+}
+
+// the bounds of the generic type and other generic parameters carry over
+#[test]
+fn with_bounds_and_params() {
+    let test: Bounded<'_, char, ()> = Bounded::Value('a', PhantomData);
+    assert_eq!(test.as_ref().copied(), test);
+    let missing: Bounded<'_, &String, ()> = Bounded::Missing(PhantomData);
+    assert_eq!(missing.cloned(), Bounded::Missing(PhantomData));
+}
+
+// transpose on #name<Result<#some_ty, E>>
+#[test]
+fn transpose_result() {
+    let test: TestType<Result<u8, char>> = Something(Ok(1));
+    assert_eq!(test.transpose(), Ok(Something(1)));
+    let test: TestType<Result<u8, char>> = Something(Err('e'));
+    assert_eq!(test.transpose(), Err('e'));
+    let test: TestType<Result<u8, char>> = Nothing;
+    assert_eq!(test.transpose(), Ok(Nothing));
+}
+
+// transpose on #name<Option<#some_ty>>
+#[test]
+fn transpose_option() {
+    assert_eq!(Something(Some(1)).transpose(), Some(Something(1)));
+    assert_eq!(Something(None::<u8>).transpose(), None);
+    assert_eq!(TestType::<Option<u8>>::Nothing.transpose(), Some(Nothing));
+}
+
+// transpose on Result<#name<#some_ty>, E>
+#[test]
+fn transpose_from_result() {
+    let test: Result<TestType<u8>, char> = Ok(Something(1));
+    assert_eq!(test.transpose(), Something(Ok(1)));
+    let test: Result<TestType<u8>, char> = Ok(Nothing);
+    assert_eq!(test.transpose(), Nothing);
+    let test: Result<TestType<u8>, char> = Err('e');
+    assert_eq!(test.transpose(), Something(Err('e')));
+}
+
+// transpose on Option<#name<#some_ty>>
+#[test]
+fn transpose_from_option() {
+    assert_eq!(Some(Something(1)).transpose(), Something(Some(1)));
+    assert_eq!(Some(TestType::<u8>::Nothing).transpose(), Nothing);
+    assert_eq!(None::<TestType<u8>>.transpose(), Something(None));
+}
+
+fn lookup(key: &str) -> TestType<Result<u8, String>> {
+    match key {
+        "" => Nothing,
+        key => Something(key.parse().map_err(|_| key.to_owned())),
+    }
+}
+
+fn lookup_twice(key: &str) -> Result<TestType<u8>, String> {
+    let first = lookup(key).transpose()?;
+    Ok(first.map(|x| x * 2))
+}
+
+// `?` on the transposed value
+#[test]
+fn transpose_with_question_mark() {
+    assert_eq!(lookup_twice("2"), Ok(Something(4)));
+    assert_eq!(lookup_twice(""), Ok(Nothing));
+    assert_eq!(lookup_twice("x"), Err("x".to_owned()));
+}
+
+mod transpose_trait {
+    use derive_optional::Optional;
+
+    #[derive(Optional, Debug, PartialEq, Eq)]
+    pub enum Remote<T: Clone> {
+        Loaded(T),
+        Idle,
+    }
+
+    #[derive(Optional, Debug, PartialEq, Eq)]
+    #[optional(no_impl(Transpose))]
+    pub enum Local<T> {
+        Value(T),
+        Empty,
+    }
+
+    // `no_impl(Transpose)` frees up the name for a trait of our own
+    #[allow(dead_code)]
+    trait LocalTranspose {}
+
+    // and the inherent `transpose` for one of our own, which would conflict otherwise
+    impl<T, E> Local<Result<T, E>> {
+        pub fn transpose(self) -> &'static str {
+            "local"
+        }
+    }
+
+    #[derive(Optional, Debug, PartialEq, Eq)]
+    #[optional(skip(transformers))]
+    pub enum Skipped<T> {
+        Value(T),
+        Empty,
+    }
+
+    // would conflict if `transformers` was generated
+    impl<T> Skipped<Option<T>> {
+        pub fn transpose(self) -> &'static str {
+            "skipped"
+        }
+    }
+}
+
+// no_impl(Transpose) and skip(transformers) leave out the inherent `transpose`
+#[test]
+fn transpose_not_added() {
+    use transpose_trait::{Local, Skipped};
+
+    assert_eq!(Local::<Result<u8, ()>>::Empty.transpose(), "local");
+    assert_eq!(Skipped::Value(Some(1)).transpose(), "skipped");
+}
+
+// the extension trait has to be imported
+#[test]
+fn transpose_trait_import() {
+    use transpose_trait::{Remote, RemoteTranspose};
+
+    let test: Result<Remote<u8>, ()> = Ok(Remote::Loaded(1));
+    assert_eq!(test.transpose(), Remote::Loaded(Ok(1)));
+    assert_eq!(Some(Remote::<u8>::Idle).transpose(), Remote::Idle);
+}
@@ -0,0 +1,176 @@
+use derive_optional::ResultLike;
+use std::marker::PhantomData;
+
+#[derive(ResultLike, Debug, PartialEq, Eq, Clone)]
+enum Response<T, E> {
+    Accepted(T),
+    Rejected { reason: E },
+}
+
+#[test]
+fn querying_and_getting() {
+    let accepted: Response<u8, &str> = Response::Accepted(1);
+    let rejected: Response<u8, &str> = Response::Rejected { reason: "full" };
+    assert!(accepted.is_accepted());
+    assert!(accepted.clone().is_accepted_and(|x| x == 1));
+    assert!(rejected.is_rejected());
+    assert!(!rejected.clone().is_rejected_and(str::is_empty));
+
+    assert_eq!(accepted.clone().unwrap(), 1);
+    assert_eq!(accepted.clone().expect("accepted"), 1);
+    assert_eq!(rejected.clone().unwrap_err(), "full");
+    assert_eq!(rejected.clone().expect_err("rejected"), "full");
+    assert_eq!(rejected.clone().unwrap_or(2), 2);
+    assert_eq!(rejected.clone().unwrap_or_else(|e| e.len() as u8), 4);
+    assert_eq!(rejected.clone().unwrap_or_default(), 0);
+
+    assert_eq!(accepted.clone().ok(), Some(1));
+    assert_eq!(accepted.err(), None);
+    assert_eq!(rejected.clone().ok(), None);
+    assert_eq!(rejected.err(), Some("full"));
+}
+
+#[test]
+#[should_panic(expected = "called `Response::unwrap_err()` on an `Accepted` value")]
+fn unwrap_err_on_ok() {
+    Response::<u8, ()>::Accepted(1).unwrap_err();
+}
+
+#[test]
+fn transformers() {
+    let accepted: Response<u8, &str> = Response::Accepted(1);
+    let rejected: Response<u8, &str> = Response::Rejected { reason: "full" };
+    assert_eq!(accepted.clone().map(|x| x == 1), Response::Accepted(true));
+    assert_eq!(
+        rejected.clone().map(|x| x == 1),
+        Response::Rejected { reason: "full" }
+    );
+    assert_eq!(accepted.clone().map_err(str::len), Response::Accepted(1));
+    assert_eq!(
+        rejected.clone().map_err(str::len),
+        Response::Rejected { reason: 4 }
+    );
+    assert_eq!(accepted.clone().map_or(0, |x| x + 1), 2);
+    assert_eq!(rejected.clone().map_or_else(|e| e.len() as u8, |x| x), 4);
+
+    let mut seen = None;
+    let _ = rejected
+        .inspect(|_| seen = Some(0))
+        .inspect_err(|e| seen = Some(e.len()));
+    assert_eq!(seen, Some(4));
+
+    let nested: Response<Option<u8>, &str> = Response::Accepted(Some(1));
+    assert_eq!(nested.transpose(), Some(accepted));
+    let nested: Response<Option<u8>, &str> = Response::Accepted(None);
+    assert_eq!(nested.transpose(), None);
+}
+
+#[test]
+fn bool_ops() {
+    let accepted: Response<u8, &str> = Response::Accepted(1);
+    let rejected: Response<u8, &str> = Response::Rejected { reason: "full" };
+    assert_eq!(
+        accepted.clone().and(Response::Accepted('a')),
+        Response::Accepted('a')
+    );
+    assert_eq!(
+        rejected.clone().and(Response::Accepted('a')),
+        Response::Rejected { reason: "full" }
+    );
+    assert_eq!(
+        accepted.clone().and_then(|x| Response::Accepted(x * 2)),
+        Response::Accepted(2)
+    );
+    assert_eq!(
+        accepted
+            .clone()
+            .or(Response::<u8, ()>::Rejected { reason: () }),
+        Response::Accepted(1)
+    );
+    assert_eq!(
+        rejected.or_else(|e| Response::<u8, ()>::Accepted(e.len() as u8)),
+        Response::Accepted(4)
+    );
+}
+
+#[test]
+fn conversions() {
+    let response: Response<u8, &str> = Ok(1).into();
+    assert_eq!(response, Response::Accepted(1));
+    assert_eq!(
+        Response::from(Err::<u8, _>("full")),
+        Response::Rejected { reason: "full" }
+    );
+    let result: Result<u8, &str> = Response::Rejected { reason: "full" }.into();
+    assert_eq!(result, Err("full"));
+}
+
+#[derive(ResultLike, Debug, PartialEq, Eq)]
+enum Ticket {
+    Issued(u32, char),
+    Refused(&'static str),
+}
+
+#[derive(ResultLike, Debug, PartialEq, Eq)]
+enum Parsed<T> {
+    Value(T),
+    Invalid(String, PhantomData<T>),
+}
+
+#[test]
+fn concrete_and_phantom_data() {
+    let ticket = Ticket::Issued(1, 'a');
+    assert_eq!(ticket.map_or(0, |(number, _)| number), 1);
+    let refused = Ticket::Refused("sold out");
+    assert_eq!(
+        refused.and(Ticket::Issued(2, 'b')),
+        Ticket::Refused("sold out")
+    );
+
+    let parsed: Parsed<u8> = Parsed::Invalid("x".to_owned(), PhantomData);
+    assert_eq!(parsed.map(u32::from).err(), Some("x".to_owned()));
+    assert_eq!(Parsed::Value(1u8).map(u32::from), Parsed::Value(1u32));
+}
+
+#[derive(ResultLike, Debug, PartialEq, Eq)]
+#[result_like(const, rename(is_ok = "succeeded"))]
+enum Status {
+    Done(u8),
+    Failed(u8),
+}
+
+#[test]
+fn const_and_rename() {
+    const DONE: bool = Status::Done(1).succeeded();
+    const FAILED: bool = Status::Failed(1).is_failed();
+    assert!(DONE && FAILED);
+    assert!(Status::Done(1).is_done_and(|x| x == 1));
+}
+
+#[derive(ResultLike, Debug, PartialEq, Eq)]
+#[result_like(rename_all(prefix = "r_", suffix = "_x"))]
+enum Checked {
+    Passed(u8),
+    Rejected(u8),
+}
+
+#[test]
+fn rename_all() {
+    assert!(Checked::Passed(1).r_is_passed_x());
+    assert!(Checked::Passed(1).r_is_passed_and_x(|x| x == 1));
+    assert!(Checked::Rejected(2).r_is_rejected_and_x(|e| e == 2));
+    assert_eq!(Checked::Rejected(2).r_unwrap_or_x(3), 3);
+}
+
+#[derive(ResultLike, Debug)]
+#[result_like(unwrap_msg = "the check was rejected")]
+enum Review {
+    Approved(u8),
+    Refused(u8),
+}
+
+#[test]
+#[should_panic(expected = "the check was rejected")]
+fn unwrap_msg() {
+    Review::Refused(1).unwrap();
+}
@@ -71,3 +71,66 @@ fn unwrap_unchecked() {
 
     // no test for Nothing, because it would be UB
 }
+
+// panics report the location of the caller
+fn panic_line<R>(f: impl FnOnce() -> R + std::panic::UnwindSafe) -> u32 {
+    use std::cell::Cell;
+    use std::sync::Once;
+
+    thread_local! {
+        static PANIC_LINE: Cell<u32> = Cell::new(0);
+    }
+    static SET_HOOK: Once = Once::new();
+    SET_HOOK.call_once(|| {
+        let default_hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            if let Some(location) = info.location() {
+                PANIC_LINE.with(|line| line.set(location.line()));
+            }
+            default_hook(info);
+        }));
+    });
+
+    assert!(std::panic::catch_unwind(f).is_err());
+    PANIC_LINE.with(|line| line.get())
+}
+
+#[test]
+fn panics_track_caller() {
+    let line = line!() + 1;
+    assert_eq!(panic_line(|| Nothing.unwrap()), line);
+
+    let line = line!() + 1;
+    assert_eq!(panic_line(|| Nothing.expect("test")), line);
+}
+
+// skipped fields are still dropped with #[optional(const)], where the methods aren't const
+thread_local! {
+    static DROPS: std::cell::Cell<usize> = std::cell::Cell::new(0);
+}
+
+#[derive(Debug, Default)]
+struct Counted;
+
+impl Drop for Counted {
+    fn drop(&mut self) {
+        DROPS.with(|drops| drops.set(drops.get() + 1));
+    }
+}
+
+#[derive(Optional, Debug)]
+#[optional(const)]
+enum Tracked {
+    Value(u8, #[optional(skip)] Counted),
+    Empty,
+}
+
+#[test]
+fn skipped_fields_dropped() {
+    let drops = || DROPS.with(|drops| drops.get());
+    let before = drops();
+    assert_eq!(Tracked::Value(1, Counted).unwrap(), 1);
+    assert_eq!(Tracked::Value(2, Counted).expect("test"), 2);
+    assert_eq!(unsafe { Tracked::Value(3, Counted).unwrap_unchecked() }, 3);
+    assert_eq!(drops() - before, 3);
+}
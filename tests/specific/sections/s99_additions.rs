@@ -31,3 +31,99 @@ fn as_option_mut() {
     let mut test = Nothing;
     assert_eq!(test.as_option_mut(), None);
 }
+
+// not `Default`, so only the methods that create a new `Missing` need it
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum MissReason {
+    Unknown,
+    Deleted,
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Lookup {
+    Found(usize),
+    #[optional(none)]
+    Missing(MissReason),
+}
+
+// reason
+#[test]
+fn reason() {
+    assert_eq!(
+        Lookup::Missing(MissReason::Deleted).reason(),
+        Some(&MissReason::Deleted)
+    );
+    assert_eq!(Lookup::Found(0).reason(), None);
+}
+
+// reason_mut
+#[test]
+fn reason_mut() {
+    let mut test = Lookup::Missing(MissReason::Unknown);
+    *test.reason_mut().unwrap() = MissReason::Deleted;
+    assert_eq!(test, Lookup::Missing(MissReason::Deleted));
+
+    let mut test = Lookup::Found(0);
+    assert_eq!(test.reason_mut(), None);
+}
+
+// into_reason
+#[test]
+fn into_reason() {
+    assert_eq!(
+        Lookup::Missing(MissReason::Deleted).into_reason(),
+        Some(MissReason::Deleted)
+    );
+    assert_eq!(Lookup::Found(0).into_reason(), None);
+}
+
+// unwrap_or_else_reason
+#[test]
+fn unwrap_or_else_reason() {
+    let fallback = |reason| match reason {
+        MissReason::Unknown => 1,
+        MissReason::Deleted => 2,
+    };
+    assert_eq!(Lookup::Found(0).unwrap_or_else_reason(fallback), 0);
+    assert_eq!(
+        Lookup::Missing(MissReason::Deleted).unwrap_or_else_reason(fallback),
+        2
+    );
+}
+
+// ok_or_reason
+#[test]
+fn ok_or_reason() {
+    assert_eq!(Lookup::Found(0).ok_or_reason(), Ok(0));
+    assert_eq!(
+        Lookup::Missing(MissReason::Deleted).ok_or_reason(),
+        Err(MissReason::Deleted)
+    );
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Versioned<T> {
+    Current(T),
+    #[optional(none)]
+    Missing(MissReason),
+}
+
+// adapters keep the reason
+#[test]
+fn reason_kept() {
+    let deleted: Versioned<u8> = Versioned::Missing(MissReason::Deleted);
+    assert_eq!(
+        deleted.map(|x| x + 1),
+        Versioned::Missing(MissReason::Deleted)
+    );
+    assert_eq!(deleted.as_ref(), Versioned::Missing(MissReason::Deleted));
+    assert_eq!(
+        deleted.and(Versioned::Current('a')),
+        Versioned::Missing(MissReason::Deleted)
+    );
+    assert_eq!(
+        Versioned::Current(1).zip(deleted),
+        Versioned::Missing(MissReason::Deleted)
+    );
+    assert_eq!(Versioned::Current(1).map(|x| x + 1), Versioned::Current(2));
+}
@@ -0,0 +1,112 @@
+use derive_optional::TriState;
+use std::marker::PhantomData;
+
+#[derive(TriState, Debug, PartialEq, Eq, Clone, Copy)]
+enum Patch<T> {
+    Set(T),
+    #[tri_state(null)]
+    Clear,
+    #[tri_state(undefined)]
+    Keep,
+}
+
+#[test]
+fn querying() {
+    assert!(Patch::Set(1).is_set());
+    assert!(!Patch::Set(1).is_clear());
+    assert!(Patch::<u8>::Clear.is_clear());
+    assert!(Patch::<u8>::Keep.is_keep());
+    assert!(!Patch::<u8>::Keep.is_set());
+}
+
+#[test]
+fn transformers() {
+    assert_eq!(Patch::Set(1).into_option(), Some(1));
+    assert_eq!(Patch::<u8>::Clear.into_option(), None);
+    assert_eq!(Patch::<u8>::Keep.into_option(), None);
+
+    assert_eq!(Patch::Set(1).map(|x| x == 1), Patch::Set(true));
+    assert_eq!(Patch::<u8>::Clear.map(|x| x == 1), Patch::Clear);
+    assert_eq!(Patch::<u8>::Keep.map(|x| x == 1), Patch::Keep);
+}
+
+#[test]
+fn merge_into() {
+    let mut target = Some(1);
+    Patch::Keep.merge_into(&mut target);
+    assert_eq!(target, Some(1));
+    Patch::Set(2).merge_into(&mut target);
+    assert_eq!(target, Some(2));
+    Patch::Clear.merge_into(&mut target);
+    assert_eq!(target, None);
+    Patch::Keep.merge_into(&mut target);
+    assert_eq!(target, None);
+}
+
+#[test]
+fn conversions() {
+    assert_eq!(Patch::from(Some(Some(1))), Patch::Set(1));
+    assert_eq!(Patch::<u8>::from(Some(None)), Patch::Clear);
+    assert_eq!(Patch::<u8>::from(None), Patch::Keep);
+
+    let opt: Option<Option<u8>> = Patch::Set(1).into();
+    assert_eq!(opt, Some(Some(1)));
+    let opt: Option<Option<u8>> = Patch::Clear.into();
+    assert_eq!(opt, Some(None));
+    let opt: Option<Option<u8>> = Patch::Keep.into();
+    assert_eq!(opt, None);
+
+    assert_eq!(Patch::<u8>::default(), Patch::Keep);
+}
+
+#[derive(TriState, Debug, PartialEq, Eq)]
+#[tri_state(const, no_impl(Default))]
+enum Field<T> {
+    #[tri_state(null)]
+    Cleared(PhantomData<T>),
+    Value {
+        value: u32,
+    },
+    #[tri_state(undefined)]
+    Missing(PhantomData<T>),
+}
+
+#[test]
+fn concrete_with_phantom_data() {
+    const CLEARED: bool = Field::<()>::Cleared(PhantomData).is_cleared();
+    assert!(CLEARED);
+    assert!(Field::<()>::Value { value: 1 }.is_value());
+    assert!(Field::<()>::Missing(PhantomData).is_missing());
+
+    let mut target = Some(1);
+    Field::<()>::Missing(PhantomData).merge_into(&mut target);
+    assert_eq!(target, Some(1));
+    Field::<()>::Cleared(PhantomData).merge_into(&mut target);
+    assert_eq!(target, None);
+    assert_eq!(Field::<()>::from(None), Field::Missing(PhantomData));
+    assert_eq!(Field::<()>::from(Some(None)), Field::Cleared(PhantomData));
+}
+
+#[derive(TriState, Debug, PartialEq, Eq)]
+enum Reordered<T> {
+    #[tri_state(undefined)]
+    Undefined,
+    #[tri_state(null)]
+    Null,
+    Defined(T),
+}
+
+#[test]
+fn marked_variants() {
+    assert!(Reordered::<u8>::Null.is_null());
+    assert!(Reordered::<u8>::Undefined.is_undefined());
+    assert_eq!(Reordered::<u8>::default(), Reordered::Undefined);
+    assert_eq!(Reordered::<u8>::from(None), Reordered::Undefined);
+    assert_eq!(Reordered::<u8>::from(Some(None)), Reordered::Null);
+
+    let mut target = Some(1);
+    Reordered::Undefined.merge_into(&mut target);
+    assert_eq!(target, Some(1));
+    Reordered::Null.merge_into(&mut target);
+    assert_eq!(target, None);
+}
@@ -0,0 +1,467 @@
+use derive_optional::Optional;
+use std::marker::PhantomData;
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Lookup<T> {
+    Present { value: T },
+    Absent,
+}
+
+#[test]
+fn named_field() {
+    let mut found = Lookup::Present { value: 1 };
+    assert!(found.is_present());
+    assert_eq!(found.as_ref(), Lookup::Present { value: &1 });
+    assert_eq!(found.map(|x| x + 1), Lookup::Present { value: 2 });
+    assert_eq!(found.unwrap_or(0), 1);
+    *found.get_or_insert(5) += 1;
+    assert_eq!(found, Lookup::Present { value: 2 });
+    assert_eq!(found.zip(Lookup::Present { value: 'a' }).unwrap(), (2, 'a'));
+    assert_eq!(found.take(), Lookup::Present { value: 2 });
+    assert_eq!(found, Lookup::Absent);
+    assert_eq!(found.insert(3), &mut 3);
+
+    let opt: Option<u8> = Lookup::Present { value: 4 }.into();
+    assert_eq!(opt, Some(4));
+    assert_eq!(Lookup::from(Some(5)), Lookup::Present { value: 5 });
+    assert_eq!(Lookup::<u8>::default(), Lookup::Absent);
+    assert_eq!(
+        Lookup::Present { value: 6 }.into_iter().collect::<Vec<_>>(),
+        vec![6]
+    );
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone)]
+enum Pair<T> {
+    Both(T, u8),
+    Neither,
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Tagged<T, Tag> {
+    Value(T, #[optional(skip)] PhantomData<Tag>),
+    Nothing,
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone)]
+enum Range {
+    Span {
+        start: usize,
+        #[optional(skip)]
+        label: String,
+        end: usize,
+    },
+    Empty,
+}
+
+#[test]
+fn multiple_fields() {
+    let pair = Pair::Both("a", 1);
+    assert!(pair.is_both());
+    assert_eq!(pair.clone().unwrap(), ("a", 1));
+    assert_eq!(pair.iter().next(), Some((&"a", &1)));
+    assert_eq!(pair.as_option_ref(), Some((&"a", &1)));
+    assert_eq!(pair.clone().map(|(s, n)| (s.len(), n)), Pair::Both(1, 1));
+    assert_eq!(pair.clone().filter(|(_, n)| *n == 2), Pair::Neither);
+    assert_eq!(pair.clone().map_or(0, |(_, n)| n), 1);
+    assert_eq!(
+        pair.clone()
+            .zip_with(Pair::Both('b', 2), |(a, n), (b, m)| ((a, b), n + m)),
+        Pair::Both(("a", 'b'), 3)
+    );
+
+    let mut pair = pair;
+    if let Some((_, n)) = pair.as_option_mut() {
+        *n += 1;
+    }
+    assert_eq!(pair, Pair::Both("a", 2));
+    assert_eq!(pair.replace(("b", 3)), Pair::Both("a", 2));
+    assert_eq!(pair.take().unwrap(), ("b", 3));
+
+    let opt: Option<(&str, u8)> = Pair::Both("c", 4).into();
+    assert_eq!(opt, Some(("c", 4)));
+    assert_eq!(Pair::from(("d", 5)), Pair::Both("d", 5));
+    assert_eq!(Pair::from(None::<(u8, u8)>), Pair::Neither);
+    assert_eq!(Pair::Both(6, 7).into_iter().next(), Some((6, 7)));
+}
+
+#[test]
+fn skipped_fields() {
+    let mut tagged: Tagged<u8, u16> = Tagged::from(1);
+    assert_eq!(tagged, Tagged::Value(1, PhantomData));
+    assert_eq!(tagged.as_ref().unwrap(), &1);
+    assert_eq!(tagged.map(|x| x * 2).unwrap(), 2);
+    *tagged.get_or_insert(5) += 1;
+    assert_eq!(tagged.unwrap(), 2);
+    assert!(tagged.contains(&2));
+
+    let range = Range::from((1, 3));
+    assert_eq!(
+        range,
+        Range::Span {
+            start: 1,
+            label: String::new(),
+            end: 3
+        }
+    );
+    let labeled = Range::Span {
+        start: 2,
+        label: "label".to_owned(),
+        end: 4,
+    };
+    assert_eq!(labeled.clone().unwrap(), (2, 4));
+    assert_eq!(labeled.clone().or(range.clone()), labeled);
+    assert_eq!(range.xor(Range::Empty).unwrap(), (1, 3));
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Cached<T, Source> {
+    Hit(T),
+    Miss(PhantomData<Source>),
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum EmptyTuple {
+    Some(u8),
+    None(),
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum EmptyStruct {
+    Some(u8),
+    None {},
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Marked<T> {
+    #[optional(none)]
+    Unset {
+        marker: PhantomData<T>,
+    },
+    Set(PhantomData<T>),
+}
+
+#[test]
+fn none_variants() {
+    let hit: Cached<u8, ()> = Cached::Hit(1);
+    let miss: Cached<u8, ()> = Cached::default();
+    assert_eq!(miss, Cached::Miss(PhantomData));
+    assert!(miss.is_miss());
+    assert_eq!(hit.map(|x| x + 1), Cached::Hit(2));
+    assert_eq!(hit.xor(miss), hit);
+    assert_eq!(miss.unwrap_or(3), 3);
+
+    assert_eq!(EmptyTuple::default(), EmptyTuple::None());
+    assert_eq!(EmptyTuple::from(Some(1)).unwrap(), 1);
+    assert!(EmptyTuple::from(None).is_none());
+    assert_eq!(EmptyStruct::default(), EmptyStruct::None {});
+    assert_eq!(
+        EmptyStruct::Some(2).filter(|x| *x > 2),
+        EmptyStruct::None {}
+    );
+
+    let unset: Marked<u8> = Marked::default();
+    assert_eq!(
+        unset,
+        Marked::Unset {
+            marker: PhantomData
+        }
+    );
+    assert!(Marked::<u8>::Set(PhantomData).is_set());
+    assert_eq!(unset.or(Marked::Set(PhantomData)).unwrap(), PhantomData);
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone)]
+enum Fetched<T> {
+    #[optional(some)]
+    Found(T),
+    NotFound {
+        reason: String,
+    },
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Annotated<T, Note> {
+    Value(T),
+    #[optional(none)]
+    Missing(Note, PhantomData<T>),
+}
+
+#[test]
+fn marked_variants() {
+    let missing: Fetched<u8> = Fetched::NotFound {
+        reason: "gone".to_owned(),
+    };
+    assert!(missing.is_not_found());
+    assert_eq!(missing.clone().unwrap_or(1), 1);
+    assert_eq!(missing.clone().or(Fetched::Found(2)), Fetched::Found(2));
+    assert_eq!(Fetched::Found(3).map(|x| x * 2), Fetched::Found(6));
+    assert_eq!(
+        Fetched::Found(4).filter(|_| false),
+        Fetched::NotFound {
+            reason: String::new()
+        }
+    );
+    assert_eq!(Fetched::Found(5).xor(missing).unwrap(), 5);
+
+    let mut annotated: Annotated<u8, u16> = Annotated::Missing(7, PhantomData);
+    assert!(annotated.is_missing());
+    assert_eq!(annotated.get_or_insert(1), &mut 1);
+    assert_eq!(annotated.take(), Annotated::Value(1));
+    assert_eq!(annotated, Annotated::default());
+    assert_eq!(annotated, Annotated::Missing(0, PhantomData));
+    let opt: Option<u8> = Annotated::<u8, u16>::Missing(3, PhantomData).into();
+    assert_eq!(opt, None);
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Remote<T> {
+    Loaded(T),
+    Loading,
+    #[optional(default)]
+    NotAsked,
+    Failed(),
+}
+
+#[test]
+fn several_none_variants() {
+    let mut remote: Remote<u8> = Remote::Loading;
+    assert!(remote.is_loading());
+    assert!(!remote.is_not_asked());
+    assert!(!remote.is_loaded());
+    assert!(Remote::<u8>::Failed().is_failed());
+
+    assert_eq!(remote.map(|x| x + 1), Remote::Loading);
+    assert_eq!(Remote::<u8>::Failed().as_ref(), Remote::Failed());
+    assert_eq!(Remote::Loaded(1).map(|x| x + 1), Remote::Loaded(2));
+    assert_eq!(remote.and_then(Remote::Loaded), Remote::Loading);
+    assert_eq!(remote.and(Remote::Loaded('a')), Remote::Loading);
+    assert_eq!(remote.filter(|_| true), Remote::Loading);
+    assert_eq!(Remote::Loaded(1).filter(|_| false), Remote::NotAsked);
+    assert_eq!(remote.xor(Remote::Failed()), Remote::NotAsked);
+    assert_eq!(remote.or(Remote::Loaded(2)), Remote::Loaded(2));
+    assert_eq!(remote.unwrap_or(3), 3);
+    assert_eq!(Remote::<u8>::default(), Remote::NotAsked);
+    assert_eq!(Remote::<u8>::from(None), Remote::NotAsked);
+    assert_eq!(Option::from(remote), None::<u8>);
+
+    assert_eq!(remote.take(), Remote::Loading);
+    assert_eq!(remote, Remote::NotAsked);
+    *remote.get_or_insert(4) += 1;
+    assert_eq!(remote, Remote::Loaded(5));
+}
+
+#[test]
+#[should_panic(expected = "called `Remote::unwrap()` on a `Loading` value")]
+fn several_none_variants_unwrap() {
+    Remote::<u8>::Loading.unwrap();
+}
+
+#[test]
+#[should_panic(expected = "called `Remote::unwrap()` on a `Failed` value")]
+fn several_none_variants_unwrap_failed() {
+    Remote::<u8>::Failed().unwrap();
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+enum Value<T> {
+    #[optional(some)]
+    Cached(T),
+    #[optional(some)]
+    Fresh {
+        value: T,
+    },
+    Absent,
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone)]
+enum Measurement {
+    #[optional(some)]
+    Exact(u8, u16),
+    #[optional(some)]
+    Estimated(u8, #[optional(skip)] String, u16),
+    Unknown,
+}
+
+#[test]
+fn several_some_variants() {
+    let cached = Value::Cached(1);
+    let fresh = Value::Fresh { value: 2 };
+    assert!(cached.is_cached());
+    assert!(fresh.is_fresh());
+    assert!(!fresh.is_absent());
+    assert_eq!(cached.unwrap() + fresh.unwrap(), 3);
+    assert_eq!(fresh.as_option_ref(), Some(&2));
+    assert_eq!(fresh.iter().collect::<Vec<_>>(), vec![&2]);
+    assert_eq!(fresh.into_iter().collect::<Vec<_>>(), vec![2]);
+
+    assert_eq!(fresh.map(|x| x * 2), Value::Fresh { value: 4 });
+    assert_eq!(cached.map(|x| x * 2), Value::Cached(2));
+    assert_eq!(fresh.as_ref(), Value::Fresh { value: &2 });
+    assert_eq!(fresh.filter(|x| *x == 2), fresh);
+    assert_eq!(fresh.filter(|x| *x == 3), Value::Absent);
+    assert_eq!(fresh.xor(Value::Absent), fresh);
+    assert_eq!(Value::Absent.xor(cached), cached);
+    assert_eq!(fresh.xor(cached), Value::Absent);
+    assert_eq!(fresh.or(cached), fresh);
+
+    assert_eq!(Value::from(3), Value::Cached(3));
+    assert_eq!(cached.zip(fresh), Value::Cached((1, 2)));
+    let mut value = fresh;
+    assert_eq!(value.take(), fresh);
+    assert_eq!(*value.get_or_insert(4), 4);
+    assert_eq!(value, Value::Cached(4));
+
+    let estimated = Measurement::Estimated(1, "rough".to_owned(), 2);
+    assert_eq!(estimated.as_option_ref(), Some((&1, &2)));
+    assert_eq!(estimated.clone().unwrap(), (1, 2));
+    assert_eq!(estimated.map_or(0, |(a, b)| u16::from(a) + b), 3);
+    assert_eq!(Measurement::Exact(3, 4).unwrap(), (3, 4));
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(some = Shown)]
+enum Visibility {
+    Hidden,
+    Shown,
+}
+
+#[test]
+fn bool_like() {
+    let shown = Visibility::Shown;
+    let hidden = Visibility::Hidden;
+    assert!(shown.is_shown());
+    assert!(!shown.is_hidden());
+    assert!(hidden.is_hidden());
+
+    assert_eq!(shown.then(|| 1), Some(1));
+    assert_eq!(hidden.then(|| 1), None);
+    assert_eq!(shown.then_some('a'), Some('a'));
+    assert_eq!(hidden.then_some('a'), None);
+
+    assert_eq!(shown.and(hidden), hidden);
+    assert_eq!(shown.and(shown), shown);
+    assert_eq!(hidden.or(shown), shown);
+    assert_eq!(hidden.or(hidden), hidden);
+    assert_eq!(shown.xor(hidden), shown);
+    assert_eq!(shown.xor(shown), hidden);
+
+    assert_eq!(Visibility::from(true), shown);
+    assert_eq!(Visibility::from(false), hidden);
+    assert!(bool::from(shown));
+    assert!(!bool::from(hidden));
+    assert_eq!(!shown, hidden);
+    assert_eq!(!hidden, shown);
+    assert_eq!(Visibility::default(), hidden);
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(some = On, const, only(querying, bool_ops), rename(is_none = "is_off_now"))]
+enum Switch<Unit> {
+    On(PhantomData<Unit>),
+    Off,
+}
+
+#[test]
+fn bool_like_options() {
+    const ON: Switch<()> = Switch::On(PhantomData);
+    const BOTH: bool = ON.and(Switch::Off).is_off_now();
+    assert!(BOTH);
+    assert!(ON.is_on());
+    assert_eq!(!ON, Switch::Off);
+    assert_eq!(Switch::<()>::default(), Switch::Off);
+}
+
+#[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+#[optional(some = Open, no_impl(Not))]
+enum Door {
+    Open,
+    Closed,
+}
+
+// would conflict if `Not` was implemented
+impl std::ops::Not for Door {
+    type Output = bool;
+
+    fn not(self) -> bool {
+        self.is_closed()
+    }
+}
+
+#[test]
+fn bool_like_no_impl() {
+    assert!(!Door::Closed);
+    assert!(!(!Door::Open));
+}
+
+#[cfg(not(msrv_build))]
+mod named_field_const {
+    #![allow(clippy::incompatible_msrv)]
+    use derive_optional::Optional;
+    use std::marker::PhantomData;
+
+    #[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+    #[optional(const)]
+    enum Named<T> {
+        Found { index: T },
+        Missing,
+    }
+
+    #[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+    #[optional(const)]
+    enum Remote<T> {
+        Loaded(T),
+        Loading,
+        #[optional(default)]
+        Idle,
+    }
+
+    #[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+    #[optional(const)]
+    enum Cached<T, Source> {
+        Hit(T),
+        Miss(PhantomData<Source>),
+    }
+
+    #[derive(Optional, Debug, PartialEq, Eq, Clone, Copy)]
+    #[optional(const)]
+    enum Coords<A, B> {
+        At(A, B),
+        Nowhere,
+    }
+
+    #[test]
+    fn unwrap() {
+        const FOUND: Named<usize> = Named::Found { index: 3 };
+        const INDEX: usize = FOUND.unwrap();
+        const IS_MISSING: bool = Named::<u8>::Missing.is_missing();
+        const IS_MISS: bool = Cached::<u8, ()>::Miss(PhantomData).is_miss();
+        const IS_LOADING: bool = Remote::<u8>::Loading.is_loading();
+        const TAKEN: (Remote<u8>, Remote<u8>) = {
+            let mut remote = Remote::Loaded(1);
+            let taken = remote.take();
+            (remote, taken)
+        };
+        assert_eq!(INDEX, 3);
+        assert!(IS_MISSING);
+        assert!(IS_MISS);
+        assert!(IS_LOADING);
+        assert_eq!(TAKEN, (Remote::Idle, Remote::Loaded(1)));
+    }
+
+    #[test]
+    fn composite() {
+        const AT: Coords<u8, i8> = Coords::At(1, -1);
+        const IS_AT: bool = AT.is_at();
+        const VALUE: (u8, i8) = AT.unwrap();
+        const REF: Option<(&u8, &i8)> = AT.as_option_ref();
+        assert!(IS_AT);
+        assert_eq!(VALUE, (1, -1));
+        assert_eq!(REF, Some((&1, &-1)));
+
+        // `replace` destructures the value, which isn't const
+        let mut coords = Coords::<String, u8>::Nowhere;
+        assert_eq!(coords.replace((String::from("a"), 1)), Coords::Nowhere);
+        assert_eq!(coords, Coords::At(String::from("a"), 1));
+    }
+}
@@ -1,6 +1,16 @@
-mod generic {}
+mod generic {
+    mod const_generics;
+    mod extra_params;
+    mod lifetimes;
+    mod substitution;
+    mod where_clauses;
+}
 
 mod specific {
+    mod attributes;
+    mod result_like;
+    mod tri_state;
+    mod variants;
     mod external {
         mod impls;
         mod traits;